@@ -0,0 +1,154 @@
+//! JSON-RPC methods for looking up the aadhaar registrations of pallet-aadhaar
+//!
+//! Every method answers from the best block unless a block hash is given.
+//! Aadhaar ids are hex encoded. Lookups linking an aadhaar id to an account
+//! are unsafe and only served when the node allows unsafe RPC calls
+
+use std::{marker::PhantomData, sync::Arc};
+
+use jsonrpsee::{
+	core::{async_trait, Error as JsonRpseeError, RpcResult},
+	proc_macros::rpc,
+	types::error::{CallError, ErrorObject},
+};
+use pallet_aadhaar::types::{AadhaarId, ConstituencyId};
+use sc_rpc_api::DenyUnsafe;
+use serde::{Deserialize, Serialize};
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_core::Bytes;
+use sp_runtime::{
+	generic::BlockId,
+	traits::{Block as BlockT, NumberFor},
+};
+
+pub use pallet_aadhaar_runtime_api::AadhaarApi as AadhaarRuntimeApi;
+
+/// Registration status as returned by aadhaar_isRegistered
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct RegistrationStatus<BlockNumber> {
+	/// The aadhaar id is registered and not revoked
+	pub registered: bool,
+	/// Constituency the aadhaar holder votes in
+	pub constituency: Option<ConstituencyId>,
+	/// Block the aadhaar id was revoked in, null unless it is revoked
+	pub revoked_at: Option<BlockNumber>,
+}
+
+#[rpc(client, server)]
+pub trait AadhaarApi<BlockHash, AccountId, BlockNumber> {
+	/// Account linked to the hex encoded aadhaar id, unsafe
+	#[method(name = "aadhaar_accountOf")]
+	fn account_of(&self, aadhaar_id: Bytes, at: Option<BlockHash>) -> RpcResult<Option<AccountId>>;
+
+	/// Hex encoded aadhaar id linked to the account, unsafe
+	#[method(name = "aadhaar_idOf")]
+	fn id_of(&self, account_id: AccountId, at: Option<BlockHash>) -> RpcResult<Option<Bytes>>;
+
+	/// Whether the hex encoded aadhaar id is registered or revoked, and its constituency
+	#[method(name = "aadhaar_isRegistered")]
+	fn is_registered(&self, aadhaar_id: Bytes, at: Option<BlockHash>) -> RpcResult<RegistrationStatus<BlockNumber>>;
+}
+
+/// Provides RPC methods to look up aadhaar registrations
+pub struct Aadhaar<C, Block> {
+	client: Arc<C>,
+	deny_unsafe: DenyUnsafe,
+	_marker: PhantomData<Block>,
+}
+
+impl<C, Block> Aadhaar<C, Block> {
+	/// Creates a new instance of the Aadhaar RPC helper
+	pub fn new(client: Arc<C>, deny_unsafe: DenyUnsafe) -> Self {
+		Self { client, deny_unsafe, _marker: Default::default() }
+	}
+}
+
+/// Error codes of the aadhaar RPC
+pub enum Error {
+	/// The call to the runtime failed
+	RuntimeError,
+	/// The aadhaar id is not 16 bytes long
+	InvalidAadhaarId,
+}
+
+impl From<Error> for i32 {
+	fn from(e: Error) -> i32 {
+		match e {
+			Error::RuntimeError => 1,
+			Error::InvalidAadhaarId => 2,
+		}
+	}
+}
+
+/// Decode a hex encoded aadhaar id
+fn parse_aadhaar_id(aadhaar_id: &Bytes) -> RpcResult<AadhaarId> {
+	AadhaarId::try_from(&aadhaar_id[..]).map_err(|_| {
+		CallError::Custom(ErrorObject::owned(
+			Error::InvalidAadhaarId.into(),
+			"Aadhaar id must be 16 bytes.",
+			Some(format!("{} bytes given", aadhaar_id.len())),
+		))
+		.into()
+	})
+}
+
+/// Report a failed runtime API call
+fn runtime_error(e: impl std::fmt::Debug) -> JsonRpseeError {
+	CallError::Custom(ErrorObject::owned(
+		Error::RuntimeError.into(),
+		"Unable to query the aadhaar runtime API.",
+		Some(format!("{:?}", e)),
+	))
+	.into()
+}
+
+#[async_trait]
+impl<C, Block, AccountId> AadhaarApiServer<<Block as BlockT>::Hash, AccountId, NumberFor<Block>> for Aadhaar<C, Block>
+where
+	Block: BlockT,
+	AccountId: codec::Codec + Serialize + serde::de::DeserializeOwned + Send + Sync + 'static,
+	C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + Send + Sync + 'static,
+	C::Api: AadhaarRuntimeApi<Block, AccountId, NumberFor<Block>>,
+{
+	fn account_of(&self, aadhaar_id: Bytes, at: Option<Block::Hash>) -> RpcResult<Option<AccountId>> {
+		self.deny_unsafe.check_if_safe()?;
+		let aadhaar_id = parse_aadhaar_id(&aadhaar_id)?;
+
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+		api.account_of(&at, aadhaar_id).map_err(runtime_error)
+	}
+
+	fn id_of(&self, account_id: AccountId, at: Option<Block::Hash>) -> RpcResult<Option<Bytes>> {
+		self.deny_unsafe.check_if_safe()?;
+
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+		let aadhaar_id = api.id_of(&at, account_id).map_err(runtime_error)?;
+
+		Ok(aadhaar_id.map(|aadhaar_id| Bytes(aadhaar_id.to_vec())))
+	}
+
+	fn is_registered(
+		&self,
+		aadhaar_id: Bytes,
+		at: Option<Block::Hash>,
+	) -> RpcResult<RegistrationStatus<NumberFor<Block>>> {
+		let aadhaar_id = parse_aadhaar_id(&aadhaar_id)?;
+
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+		let registration = api.registration(&at, aadhaar_id).map_err(runtime_error)?;
+
+		Ok(match registration {
+			Some(registration) => RegistrationStatus {
+				registered: registration.revoked_at.is_none(),
+				constituency: registration.constituency,
+				revoked_at: registration.revoked_at,
+			},
+			None => RegistrationStatus { registered: false, constituency: None, revoked_at: None },
+		})
+	}
+}
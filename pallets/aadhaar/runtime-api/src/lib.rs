@@ -0,0 +1,27 @@
+//! Runtime API for looking up the aadhaar registrations of pallet-aadhaar
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::Codec;
+use pallet_aadhaar::types::{AadhaarId, Registration};
+
+sp_api::decl_runtime_apis! {
+	/// Lookups between aadhaar ids and accounts without decoding pallet storage
+	///
+	/// Version 2 returns revoked registrations with the block they were revoked in
+	#[api_version(2)]
+	pub trait AadhaarApi<AccountId, BlockNumber>
+	where
+		AccountId: Codec,
+		BlockNumber: Codec,
+	{
+		/// Account linked to the aadhaar id
+		fn account_of(aadhaar_id: AadhaarId) -> Option<AccountId>;
+
+		/// Aadhaar id linked to the account
+		fn id_of(account_id: AccountId) -> Option<AadhaarId>;
+
+		/// Registration of the aadhaar id, revoked ones included. None when it was never registered
+		fn registration(aadhaar_id: AadhaarId) -> Option<Registration<AccountId, BlockNumber>>;
+	}
+}
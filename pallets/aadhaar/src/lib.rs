@@ -1,12 +1,14 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
-/// Terminology
-/// Aadhar Id - Unique Identity number
-/// 
-/// This pallet allows to assign aadhar id to an account number
-/// It also implements SignedExtension trait which restricts anyone 
-/// without registered aadhar id in this pallet to call any 
-/// extrinsics in the blockchain
+//! Terminology
+//! Aadhar Id - Unique Identity number
+//!
+//! This pallet allows to assign aadhar id to an account number
+//! It also implements SignedExtension trait which restricts anyone
+//! without registered aadhar id in this pallet to call any
+//! extrinsics in the blockchain
+//!
+//! Registrations can be revoked, reinstated and moved to a new account
 
 
 pub mod types;
@@ -38,6 +40,7 @@ use sp_std::marker::PhantomData;
 pub mod pallet {
 	use frame_support::pallet_prelude::*;
 	use frame_system::pallet_prelude::*;
+	use sp_runtime::traits::Saturating;
 	use sp_std::vec::Vec;
 	use crate::types::*;
 
@@ -52,6 +55,18 @@ pub mod pallet {
 
 		/// Origin who will register aadhaar
 		type RegisterOrigin: EnsureOrigin<Self::Origin>;
+
+		/// Maximum length in bytes of a constituency name
+		#[pallet::constant]
+		type MaxConstituencyNameLength: Get<u32>;
+
+		/// Maximum number of guardians of an aadhaar
+		#[pallet::constant]
+		type MaxGuardians: Get<u32>;
+
+		/// Blocks a guardian recovery waits before it can be claimed
+		#[pallet::constant]
+		type RecoveryDelay: Get<Self::BlockNumber>;
 	}
 
 	/// List of aadhaars added in the blockchain
@@ -67,6 +82,33 @@ pub mod pallet {
 	#[pallet::storage]
 	pub type RLookup<T: Config> = StorageMap<_, Blake2_128Concat, T::AccountId, AadhaarId, OptionQuery>;
 
+	/// Registry of electoral constituencies and their names
+	#[pallet::storage]
+	#[pallet::getter(fn constituency)]
+	pub type Constituencies<T: Config> =
+		StorageMap<_, Blake2_128Concat, ConstituencyId, BoundedVec<u8, T::MaxConstituencyNameLength>, OptionQuery>;
+
+	/// Constituency each aadhaar is registered to vote in
+	#[pallet::storage]
+	#[pallet::getter(fn constituency_of)]
+	pub type ConstituencyOf<T: Config> = StorageMap<_, Blake2_128Concat, AadhaarId, ConstituencyId, OptionQuery>;
+
+	/// Tombstones of revoked aadhaars
+	#[pallet::storage]
+	#[pallet::getter(fn tombstone)]
+	pub type Tombstones<T: Config> =
+		StorageMap<_, Blake2_128Concat, AadhaarId, Tombstone<T::AccountId, T::BlockNumber>, OptionQuery>;
+
+	/// Guardians able to recover each aadhaar
+	#[pallet::storage]
+	#[pallet::getter(fn guardians)]
+	pub type Guardians<T: Config> = StorageMap<_, Blake2_128Concat, AadhaarId, GuardianSet<T>, OptionQuery>;
+
+	/// Pending guardian recoveries
+	#[pallet::storage]
+	#[pallet::getter(fn recovery)]
+	pub type Recoveries<T: Config> = StorageMap<_, Blake2_128Concat, AadhaarId, Recovery<T>, OptionQuery>;
+
 
 	/// Inititalise Aadhars
 	#[pallet::genesis_config]
@@ -100,7 +142,54 @@ pub mod pallet {
 		AadhaarRegistered {
 			account_id: T::AccountId,
 			aadhaar_id: AadhaarId,
-		}
+		},
+
+		/// New constituency added to the registry
+		ConstituencyAdded {
+			constituency_id: ConstituencyId,
+		},
+
+		/// Aadhaar assigned to a constituency
+		ConstituencyAssigned {
+			aadhaar_id: AadhaarId,
+			constituency_id: ConstituencyId,
+		},
+
+		/// Aadhaar revoked and its account locked out
+		AadhaarRevoked {
+			account_id: T::AccountId,
+			aadhaar_id: AadhaarId,
+		},
+
+		/// Revoked aadhaar registered again
+		AadhaarReinstated {
+			account_id: T::AccountId,
+			aadhaar_id: AadhaarId,
+		},
+
+		/// Aadhaar moved to a new account
+		AccountRotated {
+			aadhaar_id: AadhaarId,
+			old_account: T::AccountId,
+			new_account: T::AccountId,
+		},
+
+		/// Guardians of an aadhaar set, none when cleared
+		GuardiansSet {
+			aadhaar_id: AadhaarId,
+		},
+
+		/// Guardian vouched for the recovery of an aadhaar
+		RecoveryVouched {
+			aadhaar_id: AadhaarId,
+			guardian: AadhaarId,
+			new_account: T::AccountId,
+		},
+
+		/// Pending recovery cancelled
+		RecoveryCancelled {
+			aadhaar_id: AadhaarId,
+		},
 	}
 
 	#[pallet::error]
@@ -110,6 +199,45 @@ pub mod pallet {
 
 		/// Account Id already taken
 		AccountIdRegistered,
+
+		/// Aadhaar is not registered
+		AadhaarNotFound,
+
+		/// Constituency already exists
+		ConstituencyAlreadyExists,
+
+		/// Constituency is not in the registry
+		ConstituencyNotFound,
+
+		/// Aadhaar was revoked and has to be reinstated
+		AadhaarRevoked,
+
+		/// Aadhaar is not revoked
+		AadhaarNotRevoked,
+
+		/// Threshold is zero or above the number of guardians
+		InvalidThreshold,
+
+		/// Guardians contain duplicates or the holder
+		InvalidGuardians,
+
+		/// Caller is not a guardian of the aadhaar
+		NotGuardian,
+
+		/// Guardian already vouched for the recovery
+		AlreadyVouched,
+
+		/// Recovery pending for another account
+		RecoveryMismatch,
+
+		/// No recovery pending for the aadhaar
+		RecoveryNotFound,
+
+		/// Not enough guardians vouched for the recovery
+		NotEnoughApprovals,
+
+		/// Recovery delay has not passed yet
+		RecoveryDelayed,
 	}
 
 	#[pallet::call]
@@ -135,7 +263,189 @@ pub mod pallet {
 			Ok(())
 		}
 
-		// TODO: Add Remove Aadhaar and Rotate Account Id Extrinsics
+		/// Add a constituency to the registry
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(1, 1))]
+		pub fn add_constituency(
+			origin: OriginFor<T>,
+			constituency_id: ConstituencyId,
+			name: BoundedVec<u8, T::MaxConstituencyNameLength>,
+		) -> DispatchResult {
+			// Check if origin is a from a validator
+			T::RegisterOrigin::ensure_origin(origin)?;
+
+			ensure!(
+				!Constituencies::<T>::contains_key(constituency_id),
+				Error::<T>::ConstituencyAlreadyExists
+			);
+			Constituencies::<T>::insert(constituency_id, name);
+
+			// Emit an event.
+			Self::deposit_event(Event::ConstituencyAdded { constituency_id });
+
+			Ok(())
+		}
+
+		/// Assign a registered aadhaar to the constituency it votes in
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(2, 1))]
+		pub fn assign_constituency(
+			origin: OriginFor<T>,
+			aadhaar_id: AadhaarId,
+			constituency_id: ConstituencyId,
+		) -> DispatchResult {
+			// Check if origin is a from a validator
+			T::RegisterOrigin::ensure_origin(origin)?;
+
+			ensure!(Aadhaars::<T>::contains_key(aadhaar_id), Error::<T>::AadhaarNotFound);
+			ensure!(
+				Constituencies::<T>::contains_key(constituency_id),
+				Error::<T>::ConstituencyNotFound
+			);
+			ConstituencyOf::<T>::insert(aadhaar_id, constituency_id);
+
+			// Emit an event.
+			Self::deposit_event(Event::ConstituencyAssigned { aadhaar_id, constituency_id });
+
+			Ok(())
+		}
+
+		/// Remove the registration of an aadhaar, its account can no longer call extrinsics
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(1, 4))]
+		pub fn revoke_aadhaar(
+			origin: OriginFor<T>,
+			aadhaar_id: AadhaarId,
+		) -> DispatchResult {
+			// Check if origin is a from a validator
+			T::RegisterOrigin::ensure_origin(origin)?;
+
+			let account_id = Self::do_revoke_aadhaar(aadhaar_id)?;
+
+			// Emit an event.
+			Self::deposit_event(Event::AadhaarRevoked { aadhaar_id, account_id });
+
+			Ok(())
+		}
+
+		/// Register a revoked aadhaar again, possibly to another account
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(3, 4))]
+		pub fn reinstate_aadhaar(
+			origin: OriginFor<T>,
+			account_id: T::AccountId,
+			aadhaar_id: AadhaarId,
+		) -> DispatchResult {
+			// Check if origin is a from a validator
+			T::RegisterOrigin::ensure_origin(origin)?;
+
+			ensure!(Tombstones::<T>::contains_key(aadhaar_id), Error::<T>::AadhaarNotRevoked);
+			Tombstones::<T>::remove(aadhaar_id);
+			Self::do_register_aadhaar(&account_id, aadhaar_id)?;
+
+			// Emit an event.
+			Self::deposit_event(Event::AadhaarReinstated { aadhaar_id, account_id });
+
+			Ok(())
+		}
+
+		/// Move an aadhaar to a new account, called by its account or the registration authority
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(3, 5))]
+		pub fn rotate_account(
+			origin: OriginFor<T>,
+			aadhaar_id: AadhaarId,
+			new_account: T::AccountId,
+		) -> DispatchResult {
+			// The registration authority re-verifies holders who lost their key off-chain
+			if let Err(origin) = T::RegisterOrigin::try_origin(origin) {
+				let account_id = ensure_signed(origin)?;
+				ensure!(Lookup::<T>::get(aadhaar_id) == Some(account_id), DispatchError::BadOrigin);
+			}
+
+			let old_account = Self::do_rotate_account(aadhaar_id, &new_account)?;
+
+			// Emit an event.
+			Self::deposit_event(Event::AccountRotated { aadhaar_id, old_account, new_account });
+
+			Ok(())
+		}
+
+		/// Name the guardians who may recover the caller's aadhaar, none disables recovery
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(1, 2))]
+		pub fn set_guardians(
+			origin: OriginFor<T>,
+			guardians: BoundedVec<AadhaarId, T::MaxGuardians>,
+			threshold: u32,
+		) -> DispatchResult {
+			let account_id = ensure_signed(origin)?;
+			let aadhaar_id = RLookup::<T>::get(&account_id).ok_or(Error::<T>::AadhaarNotFound)?;
+
+			Self::do_set_guardians(aadhaar_id, guardians, threshold)?;
+
+			// Emit an event.
+			Self::deposit_event(Event::GuardiansSet { aadhaar_id });
+
+			Ok(())
+		}
+
+		/// Vouch as guardian for moving the aadhaar to a new account
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(3, 1))]
+		pub fn vouch_recovery(
+			origin: OriginFor<T>,
+			aadhaar_id: AadhaarId,
+			new_account: T::AccountId,
+		) -> DispatchResult {
+			let account_id = ensure_signed(origin)?;
+			let guardian = RLookup::<T>::get(&account_id).ok_or(Error::<T>::NotGuardian)?;
+
+			Self::do_vouch_recovery(aadhaar_id, guardian, &new_account)?;
+
+			// Emit an event.
+			Self::deposit_event(Event::RecoveryVouched { aadhaar_id, guardian, new_account });
+
+			Ok(())
+		}
+
+		/// Move the aadhaar to the recovered account once enough guardians vouched and the
+		/// recovery delay passed
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(5, 5))]
+		pub fn claim_recovery(
+			origin: OriginFor<T>,
+			aadhaar_id: AadhaarId,
+		) -> DispatchResult {
+			ensure_signed(origin)?;
+
+			let recovery = Recoveries::<T>::get(aadhaar_id).ok_or(Error::<T>::RecoveryNotFound)?;
+			let threshold = Guardians::<T>::get(aadhaar_id).map_or(u32::MAX, |set| set.threshold);
+			ensure!(recovery.approvals.len() as u32 >= threshold, Error::<T>::NotEnoughApprovals);
+			ensure!(
+				frame_system::Pallet::<T>::block_number() >= recovery.started_at.saturating_add(T::RecoveryDelay::get()),
+				Error::<T>::RecoveryDelayed
+			);
+
+			let old_account = Self::do_rotate_account(aadhaar_id, &recovery.new_account)?;
+
+			// Emit an event.
+			Self::deposit_event(Event::AccountRotated { aadhaar_id, old_account, new_account: recovery.new_account });
+
+			Ok(())
+		}
+
+		/// Cancel a pending recovery, called by the aadhaar's account or the registration authority
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(2, 1))]
+		pub fn cancel_recovery(
+			origin: OriginFor<T>,
+			aadhaar_id: AadhaarId,
+		) -> DispatchResult {
+			if let Err(origin) = T::RegisterOrigin::try_origin(origin) {
+				let account_id = ensure_signed(origin)?;
+				ensure!(Lookup::<T>::get(aadhaar_id) == Some(account_id), DispatchError::BadOrigin);
+			}
+
+			ensure!(Recoveries::<T>::contains_key(aadhaar_id), Error::<T>::RecoveryNotFound);
+			Recoveries::<T>::remove(aadhaar_id);
+
+			// Emit an event.
+			Self::deposit_event(Event::RecoveryCancelled { aadhaar_id });
+
+			Ok(())
+		}
 	}
 
 	impl<T: Config> Pallet<T> {
@@ -178,6 +488,12 @@ pub mod pallet {
 			aadhaar_id: AadhaarId,
 		) -> DispatchResult {
 
+			// ensure aadhaar was not revoked
+			ensure!(
+				!Tombstones::<T>::contains_key(aadhaar_id),
+				Error::<T>::AadhaarRevoked
+			);
+
 			// ensure aadhaar is not already taken
 			ensure!(
 				!Lookup::<T>::contains_key(aadhaar_id), 
@@ -214,6 +530,99 @@ pub mod pallet {
 			Ok(())
 		}
 
+		/// Unlinks the aadhaar from its account and leaves a tombstone, returns the account
+		/// The constituency assignment is kept for a later reinstatement
+		pub fn do_revoke_aadhaar(aadhaar_id: AadhaarId) -> Result<T::AccountId, DispatchError> {
+			let aadhaar = Aadhaars::<T>::take(aadhaar_id).ok_or(Error::<T>::AadhaarNotFound)?;
+
+			// Removing the reverse lookup locks the account out through CheckAccess
+			Lookup::<T>::remove(aadhaar_id);
+			RLookup::<T>::remove(&aadhaar.account_id);
+			Recoveries::<T>::remove(aadhaar_id);
+
+			Tombstones::<T>::insert(
+				aadhaar_id,
+				Tombstone {
+					account_id: aadhaar.account_id.clone(),
+					revoked_at: frame_system::Pallet::<T>::block_number(),
+				},
+			);
+
+			Ok(aadhaar.account_id)
+		}
+
+		/// Links the aadhaar to a new account in place of the old one, returns the old account
+		/// Pending recoveries are dropped
+		pub fn do_rotate_account(
+			aadhaar_id: AadhaarId,
+			new_account: &T::AccountId,
+		) -> Result<T::AccountId, DispatchError> {
+			// ensure the new account id is not already linked to a Aadhar
+			ensure!(
+				!RLookup::<T>::contains_key(new_account),
+				Error::<T>::AccountIdRegistered
+			);
+
+			let old_account = Aadhaars::<T>::try_mutate(aadhaar_id, |aadhaar| {
+				let aadhaar = aadhaar.as_mut().ok_or(Error::<T>::AadhaarNotFound)?;
+				Ok::<_, DispatchError>(sp_std::mem::replace(&mut aadhaar.account_id, new_account.clone()))
+			})?;
+
+			Lookup::<T>::insert(aadhaar_id, new_account);
+			RLookup::<T>::remove(&old_account);
+			RLookup::<T>::insert(new_account, aadhaar_id);
+			Recoveries::<T>::remove(aadhaar_id);
+
+			Ok(old_account)
+		}
+
+		/// Replaces the guardians of the aadhaar and drops its pending recovery
+		pub fn do_set_guardians(
+			aadhaar_id: AadhaarId,
+			mut guardians: BoundedVec<AadhaarId, T::MaxGuardians>,
+			threshold: u32,
+		) -> DispatchResult {
+			Recoveries::<T>::remove(aadhaar_id);
+			if guardians.is_empty() {
+				Guardians::<T>::remove(aadhaar_id);
+				return Ok(())
+			}
+
+			ensure!(threshold > 0 && threshold as usize <= guardians.len(), Error::<T>::InvalidThreshold);
+			guardians.sort();
+			ensure!(
+				guardians.windows(2).all(|pair| pair[0] != pair[1]) && !guardians.contains(&aadhaar_id),
+				Error::<T>::InvalidGuardians
+			);
+			Guardians::<T>::insert(aadhaar_id, GuardianSet { guardians, threshold });
+
+			Ok(())
+		}
+
+		/// Records the guardian's approval, the first approval starts the recovery delay
+		pub fn do_vouch_recovery(
+			aadhaar_id: AadhaarId,
+			guardian: AadhaarId,
+			new_account: &T::AccountId,
+		) -> DispatchResult {
+			let set = Guardians::<T>::get(aadhaar_id).ok_or(Error::<T>::NotGuardian)?;
+			ensure!(set.guardians.binary_search(&guardian).is_ok(), Error::<T>::NotGuardian);
+			ensure!(Lookup::<T>::contains_key(aadhaar_id), Error::<T>::AadhaarNotFound);
+
+			let mut recovery = Recoveries::<T>::get(aadhaar_id).unwrap_or_else(|| Recovery {
+				new_account: new_account.clone(),
+				approvals: Default::default(),
+				started_at: frame_system::Pallet::<T>::block_number(),
+			});
+			ensure!(&recovery.new_account == new_account, Error::<T>::RecoveryMismatch);
+			ensure!(!recovery.approvals.contains(&guardian), Error::<T>::AlreadyVouched);
+			// Guardians are bounded by the same limit
+			recovery.approvals.try_push(guardian).map_err(|_| Error::<T>::AlreadyVouched)?;
+			Recoveries::<T>::insert(aadhaar_id, recovery);
+
+			Ok(())
+		}
+
 		/// Check if given account has registered aadhaar
 		pub fn does_aadhaar_exists(account_id: &T::AccountId) -> bool {
 			match Self::get_aadhaar_id(account_id) {
@@ -226,6 +635,30 @@ pub mod pallet {
 		pub fn get_aadhaar_id(account_id: &T::AccountId) -> Option<AadhaarId> {
 			RLookup::<T>::get(account_id)
 		}
+
+		/// Check if given constituency is in the registry
+		pub fn does_constituency_exist(constituency_id: ConstituencyId) -> bool {
+			Constituencies::<T>::contains_key(constituency_id)
+		}
+
+		/// Get account id linked to aadhaar id
+		pub fn get_account_id(aadhaar_id: &AadhaarId) -> Option<T::AccountId> {
+			Lookup::<T>::get(aadhaar_id)
+		}
+
+		/// Get the registration of an aadhaar id, revoked ones included. None when it was never
+		/// registered
+		pub fn registration(aadhaar_id: &AadhaarId) -> Option<Registration<T::AccountId, T::BlockNumber>> {
+			let constituency = ConstituencyOf::<T>::get(aadhaar_id);
+			match Lookup::<T>::get(aadhaar_id) {
+				Some(account_id) => Some(Registration { account_id, constituency, revoked_at: None }),
+				None => Tombstones::<T>::get(aadhaar_id).map(|tombstone| Registration {
+					account_id: tombstone.account_id,
+					constituency,
+					revoked_at: Some(tombstone.revoked_at),
+				}),
+			}
+		}
 	}
 }
 
@@ -275,6 +708,7 @@ impl<T: Config + Send + Sync + scale_info::TypeInfo> SignedExtension for CheckAc
 		info: &DispatchInfoOf<Self::Call>,
 		_len: usize,
 	) -> TransactionValidity {
+		// Revoked aadhaars lose their reverse lookup, pooled transactions fail in pre_dispatch
 		if RLookup::<T>::contains_key(who) {
 			Ok(ValidTransaction {
 				priority: info.weight as TransactionPriority,
@@ -62,6 +62,9 @@ impl system::Config for Test {
 impl pallet_aadhaar::Config for Test {
 	type Event = Event;
 	type RegisterOrigin = EnsureSigned<Self::AccountId>;
+	type MaxConstituencyNameLength = ConstU32<32>;
+	type MaxGuardians = ConstU32<3>;
+	type RecoveryDelay = ConstU64<10>;
 }
 
 // Build genesis storage according to the mock runtime.
@@ -92,3 +92,259 @@ fn test_register_existing_account_id_fails() {
         );
 	})
 }
+
+#[test]
+fn test_assign_constituency() {
+    new_test_ext().execute_with(|| {
+        let constituency_id: ConstituencyId = 1;
+
+        assert_noop!(Aadhaar::assign_constituency(
+			    Origin::signed(INITIAL_USER_ACCOUNT),
+                INITIAL_USER_AADHAAR,
+                constituency_id,
+		    ),
+            Error::<Test>::ConstituencyNotFound,
+        );
+
+        assert_ok!(Aadhaar::add_constituency(
+			Origin::signed(INITIAL_USER_ACCOUNT),
+            constituency_id,
+            b"Varanasi".to_vec().try_into().unwrap(),
+		));
+        assert_noop!(Aadhaar::add_constituency(
+			    Origin::signed(INITIAL_USER_ACCOUNT),
+                constituency_id,
+                b"Amethi".to_vec().try_into().unwrap(),
+		    ),
+            Error::<Test>::ConstituencyAlreadyExists,
+        );
+
+        assert_noop!(Aadhaar::assign_constituency(
+			    Origin::signed(INITIAL_USER_ACCOUNT),
+                *b"2222222222222222",
+                constituency_id,
+		    ),
+            Error::<Test>::AadhaarNotFound,
+        );
+        assert_ok!(Aadhaar::assign_constituency(
+			Origin::signed(INITIAL_USER_ACCOUNT),
+            INITIAL_USER_AADHAAR,
+            constituency_id,
+		));
+
+		assert_eq!(ConstituencyOf::<Test>::get(INITIAL_USER_AADHAAR), Some(constituency_id));
+	})
+}
+
+#[test]
+fn test_registration_lookups() {
+    new_test_ext().execute_with(|| {
+        let unregistered = *b"9999999999999999";
+
+        assert_eq!(Aadhaar::get_account_id(&INITIAL_USER_AADHAAR), Some(INITIAL_USER_ACCOUNT));
+        assert_eq!(Aadhaar::get_account_id(&unregistered), None);
+        assert_eq!(Aadhaar::registration(&unregistered), None);
+        assert_eq!(Aadhaar::registration(&INITIAL_USER_AADHAAR), Some(Registration {
+            account_id: INITIAL_USER_ACCOUNT,
+            constituency: None,
+            revoked_at: None,
+        }));
+	})
+}
+
+#[test]
+fn test_revoke_aadhaar() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let call: crate::mock::Call = frame_system::Call::remark { remark: vec![] }.into();
+        let info = DispatchInfo::default();
+        assert_ok!(CheckAccess::<Test>::new().validate(&INITIAL_USER_ACCOUNT, &call, &info, 0));
+
+        assert_ok!(Aadhaar::revoke_aadhaar(
+			Origin::signed(INITIAL_USER_ACCOUNT),
+            INITIAL_USER_AADHAAR,
+		));
+        System::assert_last_event(crate::mock::Event::Aadhaar(crate::Event::AadhaarRevoked {
+            account_id: INITIAL_USER_ACCOUNT,
+            aadhaar_id: INITIAL_USER_AADHAAR,
+        }));
+
+		assert_eq!(Aadhaars::<Test>::contains_key(INITIAL_USER_AADHAAR), false);
+		assert_eq!(Lookup::<Test>::contains_key(INITIAL_USER_AADHAAR), false);
+		assert_eq!(RLookup::<Test>::contains_key(INITIAL_USER_ACCOUNT), false);
+        assert_eq!(Aadhaar::tombstone(INITIAL_USER_AADHAAR), Some(Tombstone {
+            account_id: INITIAL_USER_ACCOUNT,
+            revoked_at: 1,
+        }));
+        assert_eq!(Aadhaar::registration(&INITIAL_USER_AADHAAR), Some(Registration {
+            account_id: INITIAL_USER_ACCOUNT,
+            constituency: None,
+            revoked_at: Some(1),
+        }));
+
+        // The account is locked out at once
+        assert_eq!(
+            CheckAccess::<Test>::new().validate(&INITIAL_USER_ACCOUNT, &call, &info, 0),
+            Err(InvalidTransaction::Custom(0).into()),
+        );
+
+        assert_noop!(Aadhaar::revoke_aadhaar(
+			    Origin::signed(INITIAL_USER_ACCOUNT),
+                INITIAL_USER_AADHAAR,
+		    ),
+            Error::<Test>::AadhaarNotFound,
+        );
+        assert_noop!(Aadhaar::register_aadhaar(
+			    Origin::signed(INITIAL_USER_ACCOUNT),
+                2,
+                INITIAL_USER_AADHAAR,
+		    ),
+            Error::<Test>::AadhaarRevoked,
+        );
+        assert_noop!(Aadhaar::reinstate_aadhaar(
+			    Origin::signed(INITIAL_USER_ACCOUNT),
+                2,
+                *b"2222222222222222",
+		    ),
+            Error::<Test>::AadhaarNotRevoked,
+        );
+
+        assert_ok!(Aadhaar::reinstate_aadhaar(
+			Origin::signed(INITIAL_USER_ACCOUNT),
+            2,
+            INITIAL_USER_AADHAAR,
+		));
+        System::assert_last_event(crate::mock::Event::Aadhaar(crate::Event::AadhaarReinstated {
+            account_id: 2,
+            aadhaar_id: INITIAL_USER_AADHAAR,
+        }));
+
+		assert_eq!(Aadhaar::tombstone(INITIAL_USER_AADHAAR), None);
+        assert_eq!(Aadhaar::get_account_id(&INITIAL_USER_AADHAAR), Some(2));
+        assert_ok!(CheckAccess::<Test>::new().validate(&2, &call, &info, 0));
+	})
+}
+
+#[test]
+fn test_rotate_account() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let new_account_id: AccounId = 2;
+
+        // Only the linked account or the registration authority may rotate
+        assert_ok!(Aadhaar::register_aadhaar(
+			Origin::signed(INITIAL_USER_ACCOUNT),
+            3,
+            *b"3333333333333333",
+		));
+        assert_noop!(Aadhaar::rotate_account(
+			    Origin::signed(3),
+                INITIAL_USER_AADHAAR,
+                new_account_id,
+		    ),
+            sp_runtime::DispatchError::BadOrigin,
+        );
+        assert_noop!(Aadhaar::rotate_account(
+			    Origin::signed(INITIAL_USER_ACCOUNT),
+                INITIAL_USER_AADHAAR,
+                3,
+		    ),
+            Error::<Test>::AccountIdRegistered,
+        );
+
+        assert_ok!(Aadhaar::rotate_account(
+			Origin::signed(INITIAL_USER_ACCOUNT),
+            INITIAL_USER_AADHAAR,
+            new_account_id,
+		));
+        System::assert_last_event(crate::mock::Event::Aadhaar(crate::Event::AccountRotated {
+            aadhaar_id: INITIAL_USER_AADHAAR,
+            old_account: INITIAL_USER_ACCOUNT,
+            new_account: new_account_id,
+        }));
+
+        assert_eq!(Aadhaar::get_account_id(&INITIAL_USER_AADHAAR), Some(new_account_id));
+        assert_eq!(Aadhaar::get_aadhaar_id(&new_account_id), Some(INITIAL_USER_AADHAAR));
+        assert_eq!(Aadhaar::get_aadhaar_id(&INITIAL_USER_ACCOUNT), None);
+        assert_eq!(Aadhaar::aadhaar(INITIAL_USER_AADHAAR).unwrap().account_id, new_account_id);
+	})
+}
+
+#[test]
+fn test_guardian_recovery() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let guardians: Vec<(AccounId, AadhaarId)> =
+            vec![(2, *b"2222222222222222"), (3, *b"3333333333333333"), (4, *b"4444444444444444")];
+        for (account_id, aadhaar_id) in guardians.iter() {
+            assert_ok!(Aadhaar::register_aadhaar(Origin::signed(INITIAL_USER_ACCOUNT), *account_id, *aadhaar_id));
+        }
+        let guardian_ids: Vec<AadhaarId> = guardians.iter().map(|(_, aadhaar_id)| *aadhaar_id).collect();
+        let new_account_id: AccounId = 5;
+
+        assert_noop!(Aadhaar::set_guardians(
+			    Origin::signed(INITIAL_USER_ACCOUNT),
+                guardian_ids.clone().try_into().unwrap(),
+                4,
+		    ),
+            Error::<Test>::InvalidThreshold,
+        );
+        assert_noop!(Aadhaar::set_guardians(
+			    Origin::signed(INITIAL_USER_ACCOUNT),
+                vec![guardian_ids[0], guardian_ids[0]].try_into().unwrap(),
+                1,
+		    ),
+            Error::<Test>::InvalidGuardians,
+        );
+        assert_ok!(Aadhaar::set_guardians(
+			Origin::signed(INITIAL_USER_ACCOUNT),
+            guardian_ids.clone().try_into().unwrap(),
+            2,
+		));
+
+        assert_noop!(Aadhaar::vouch_recovery(
+			    Origin::signed(INITIAL_USER_ACCOUNT),
+                INITIAL_USER_AADHAAR,
+                new_account_id,
+		    ),
+            Error::<Test>::NotGuardian,
+        );
+        assert_ok!(Aadhaar::vouch_recovery(Origin::signed(2), INITIAL_USER_AADHAAR, new_account_id));
+        assert_noop!(
+            Aadhaar::vouch_recovery(Origin::signed(2), INITIAL_USER_AADHAAR, new_account_id),
+            Error::<Test>::AlreadyVouched,
+        );
+        assert_noop!(
+            Aadhaar::vouch_recovery(Origin::signed(3), INITIAL_USER_AADHAAR, 6),
+            Error::<Test>::RecoveryMismatch,
+        );
+        assert_noop!(
+            Aadhaar::claim_recovery(Origin::signed(2), INITIAL_USER_AADHAAR),
+            Error::<Test>::NotEnoughApprovals,
+        );
+
+        // The holder still owning the key can cancel during the delay
+        assert_ok!(Aadhaar::cancel_recovery(Origin::signed(INITIAL_USER_ACCOUNT), INITIAL_USER_AADHAAR));
+        assert_eq!(Aadhaar::recovery(INITIAL_USER_AADHAAR), None);
+
+        assert_ok!(Aadhaar::vouch_recovery(Origin::signed(2), INITIAL_USER_AADHAAR, new_account_id));
+        assert_ok!(Aadhaar::vouch_recovery(Origin::signed(4), INITIAL_USER_AADHAAR, new_account_id));
+        assert_noop!(
+            Aadhaar::claim_recovery(Origin::signed(2), INITIAL_USER_AADHAAR),
+            Error::<Test>::RecoveryDelayed,
+        );
+
+        System::set_block_number(11);
+        assert_ok!(Aadhaar::claim_recovery(Origin::signed(2), INITIAL_USER_AADHAAR));
+        System::assert_last_event(crate::mock::Event::Aadhaar(crate::Event::AccountRotated {
+            aadhaar_id: INITIAL_USER_AADHAAR,
+            old_account: INITIAL_USER_ACCOUNT,
+            new_account: new_account_id,
+        }));
+
+        assert_eq!(Aadhaar::get_account_id(&INITIAL_USER_AADHAAR), Some(new_account_id));
+        assert_eq!(Aadhaar::recovery(INITIAL_USER_AADHAAR), None);
+        // Guardians stay for later recoveries
+        assert_eq!(Aadhaar::guardians(INITIAL_USER_AADHAAR).unwrap().threshold, 2);
+	})
+}
@@ -1,10 +1,35 @@
 use super::*;
+use frame_support::{BoundedVec, CloneNoBound, EqNoBound, PartialEqNoBound, RuntimeDebugNoBound};
 
 use sp_core::sr25519;
 
 pub type PublicKey = sr25519::Public;
 pub type AadhaarId = [u8; 16];
 
+/// Electoral constituency a voter is registered in
+pub type ConstituencyId = u32;
+
+/// Registration of an aadhaar id served by the aadhaar runtime API
+#[derive(Encode, Decode, Clone, PartialEq, Eq, Debug, TypeInfo)]
+pub struct Registration<AccountId, BlockNumber> {
+    /// Account the aadhaar id is linked to, or was until it got revoked
+    pub account_id: AccountId,
+    /// Constituency the aadhaar holder votes in
+    pub constituency: Option<ConstituencyId>,
+    /// Block the aadhaar id was revoked in, None while it is registered
+    pub revoked_at: Option<BlockNumber>,
+}
+
+/// Left behind by a revoked aadhaar id so it is not registered again
+/// without being reinstated
+#[derive(Encode, Decode, Clone, PartialEq, Eq, Debug, TypeInfo, MaxEncodedLen)]
+pub struct Tombstone<AccountId, BlockNumber> {
+    /// Account the aadhaar id was linked to
+    pub account_id: AccountId,
+    /// Block the aadhaar id was revoked in
+    pub revoked_at: BlockNumber,
+}
+
 /// Aadhaar type to register user
 /// TODO: Add username
 #[derive(Encode, Decode, Clone, PartialEq, Eq, Debug, TypeInfo, MaxEncodedLen)]
@@ -15,3 +40,31 @@ pub struct Aadhaar<AccountId> {
     /// Linked user for the account
     pub account_id: AccountId,
 }
+
+/// Guardians an aadhaar holder trusts to move the aadhaar to a new account
+#[derive(
+    Encode, Decode, CloneNoBound, PartialEqNoBound, EqNoBound, RuntimeDebugNoBound, TypeInfo, MaxEncodedLen,
+)]
+#[scale_info(skip_type_params(T))]
+#[codec(mel_bound(T: Config))]
+pub struct GuardianSet<T: Config> {
+    /// Aadhaar ids of the guardians, sorted
+    pub guardians: BoundedVec<AadhaarId, T::MaxGuardians>,
+    /// Guardians needed to approve a recovery
+    pub threshold: u32,
+}
+
+/// Pending recovery of an aadhaar to a new account
+#[derive(
+    Encode, Decode, CloneNoBound, PartialEqNoBound, EqNoBound, RuntimeDebugNoBound, TypeInfo, MaxEncodedLen,
+)]
+#[scale_info(skip_type_params(T))]
+#[codec(mel_bound(T: Config))]
+pub struct Recovery<T: Config> {
+    /// Account the aadhaar is moved to
+    pub new_account: T::AccountId,
+    /// Guardians who vouched for the new account
+    pub approvals: BoundedVec<AadhaarId, T::MaxGuardians>,
+    /// Block the first guardian vouched in
+    pub started_at: T::BlockNumber,
+}
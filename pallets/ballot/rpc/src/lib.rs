@@ -0,0 +1,402 @@
+//! JSON-RPC methods for querying the voting sessions of pallet-ballot
+//!
+//! Every method answers from the best block unless a block hash is given.
+//! Aadhaar ids are hex encoded and tallies are sent as numbers or hex
+//! strings so large tallies survive JSON clients
+//!
+//! ballot_subscribeTally follows the best or the finalized chain through the
+//! client's block notifications and pushes the tally of a session every time
+//! an imported block changes it
+
+use std::{marker::PhantomData, sync::Arc};
+
+use futures::{future, stream, FutureExt, StreamExt};
+use jsonrpsee::{
+	core::{async_trait, Error as JsonRpseeError, RpcResult},
+	proc_macros::rpc,
+	types::{
+		error::{CallError, ErrorObject},
+		SubscriptionResult,
+	},
+	SubscriptionSink,
+};
+use pallet_aadhaar::types::{AadhaarId, ConstituencyId};
+use pallet_ballot::types::{
+	BallotType, Candidate, ElectionResult, ReferendumOutcome, ReferendumTally, SessionInfo, VoteIndex, VoteState,
+};
+use sc_client_api::BlockchainEvents;
+use sc_rpc::SubscriptionTaskExecutor;
+use serde::{Deserialize, Serialize};
+use sp_api::{ApiExt, ProvideRuntimeApi};
+use sp_blockchain::HeaderBackend;
+use sp_core::Bytes;
+use sp_rpc::number::NumberOrHex;
+use sp_runtime::{
+	generic::BlockId,
+	traits::{Block as BlockT, NumberFor},
+	Perbill,
+};
+
+pub use pallet_ballot_runtime_api::BallotApi as BallotRuntimeApi;
+
+/// Voting session as returned by ballot_getSession
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct SessionView<BlockNumber, Hash> {
+	pub vote_index: VoteIndex,
+	pub state: VoteState,
+	pub ballot_type: BallotType,
+	pub commissioner: Option<Bytes>,
+	pub starts_at: Option<BlockNumber>,
+	pub ends_at: Option<BlockNumber>,
+	pub secret: bool,
+	pub majority: Option<Perbill>,
+	pub constituencies: Vec<ConstituencyId>,
+	pub electoral_roll: Option<Hash>,
+	pub turnout: u32,
+	pub parent: Option<VoteIndex>,
+	pub runoff: Option<VoteIndex>,
+}
+
+impl<BlockNumber, Hash> From<SessionInfo<BlockNumber, Hash>> for SessionView<BlockNumber, Hash> {
+	fn from(info: SessionInfo<BlockNumber, Hash>) -> Self {
+		SessionView {
+			vote_index: info.vote_index,
+			state: info.state,
+			ballot_type: info.ballot_type,
+			commissioner: info.commissioner.map(hex_id),
+			starts_at: info.starts_at,
+			ends_at: info.ends_at,
+			secret: info.secret,
+			majority: info.majority,
+			constituencies: info.constituencies,
+			electoral_roll: info.electoral_roll,
+			turnout: info.turnout,
+			parent: info.parent,
+			runoff: info.runoff,
+		}
+	}
+}
+
+/// Votes of one candidate
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct CandidateTally {
+	pub aadhaar_id: Bytes,
+	pub votes: NumberOrHex,
+}
+
+impl From<Candidate> for CandidateTally {
+	fn from(candidate: Candidate) -> Self {
+		CandidateTally { aadhaar_id: hex_id(candidate.aadhaar_id), votes: candidate.vote_count.into() }
+	}
+}
+
+/// Yes, No and Abstain votes of a referendum
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ReferendumView {
+	pub yes: NumberOrHex,
+	pub no: NumberOrHex,
+	pub abstain: NumberOrHex,
+	/// Whether the proposal passed, null until the referendum is decided
+	pub approved: Option<bool>,
+}
+
+impl From<ReferendumTally> for ReferendumView {
+	fn from(tally: ReferendumTally) -> Self {
+		ReferendumView { yes: tally.yes.into(), no: tally.no.into(), abstain: tally.abstain.into(), approved: None }
+	}
+}
+
+impl From<ReferendumOutcome> for ReferendumView {
+	fn from(outcome: ReferendumOutcome) -> Self {
+		ReferendumView { approved: Some(outcome.approved), ..outcome.tally.into() }
+	}
+}
+
+/// Candidate tallies and turnout as returned by ballot_getTally
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct TallyView {
+	pub vote_index: VoteIndex,
+	/// Candidates ordered by votes
+	pub candidates: Vec<CandidateTally>,
+	/// Votes for None of the Above
+	pub nota: NumberOrHex,
+	/// Votes of a referendum, null for candidate elections
+	pub referendum: Option<ReferendumView>,
+	pub turnout: u32,
+}
+
+/// Chain a tally subscription follows
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Debug)]
+#[serde(rename_all = "camelCase")]
+pub enum Follow {
+	/// New best blocks, tallies can be reverted by a reorg
+	Best,
+	/// Finalized blocks
+	Finalized,
+}
+
+impl Default for Follow {
+	fn default() -> Self {
+		Follow::Best
+	}
+}
+
+/// Outcome of a decided session as returned by ballot_getResults
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ResultView {
+	pub standings: Vec<CandidateTally>,
+	pub nota: NumberOrHex,
+	pub winners: Vec<Bytes>,
+	pub turnout: u32,
+	pub tied: bool,
+	/// Decision of a referendum, null for candidate elections
+	pub referendum: Option<ReferendumView>,
+}
+
+impl From<ElectionResult> for ResultView {
+	fn from(result: ElectionResult) -> Self {
+		ResultView {
+			standings: result.standings.into_iter().map(Into::into).collect(),
+			nota: result.nota.into(),
+			winners: result.winners.into_iter().map(hex_id).collect(),
+			turnout: result.turnout,
+			tied: result.tied,
+			referendum: result.referendum.map(Into::into),
+		}
+	}
+}
+
+#[rpc(client, server)]
+pub trait BallotApi<BlockHash, BlockNumber, Hash> {
+	/// Overview of the session, null when it does not exist or was reset
+	#[method(name = "ballot_getSession")]
+	fn session(&self, vote_index: VoteIndex, at: Option<BlockHash>) -> RpcResult<Option<SessionView<BlockNumber, Hash>>>;
+
+	/// Sessions accepting ballots or counting them
+	#[method(name = "ballot_listSessions")]
+	fn list_sessions(&self, at: Option<BlockHash>) -> RpcResult<Vec<VoteIndex>>;
+
+	/// Candidate, NOTA and referendum tallies and turnout of the session
+	#[method(name = "ballot_getTally")]
+	fn tally(&self, vote_index: VoteIndex, at: Option<BlockHash>) -> RpcResult<TallyView>;
+
+	/// Whether the hex encoded aadhaar id cast a ballot on the session
+	#[method(name = "ballot_hasVoted")]
+	fn has_voted(&self, vote_index: VoteIndex, aadhaar_id: Bytes, at: Option<BlockHash>) -> RpcResult<bool>;
+
+	/// Result of a decided session, null until it is decided
+	#[method(name = "ballot_getResults")]
+	fn results(&self, vote_index: VoteIndex, at: Option<BlockHash>) -> RpcResult<Option<ResultView>>;
+
+	/// Tally of the session now and whenever a followed block changes it, best blocks by default
+	#[subscription(
+		name = "ballot_subscribeTally" => "ballot_tally",
+		unsubscribe = "ballot_unsubscribeTally",
+		item = TallyView,
+	)]
+	fn subscribe_tally(&self, vote_index: VoteIndex, follow: Option<Follow>);
+}
+
+/// Provides RPC methods to query voting sessions
+pub struct Ballot<C, Block> {
+	client: Arc<C>,
+	executor: SubscriptionTaskExecutor,
+	_marker: PhantomData<Block>,
+}
+
+impl<C, Block> Ballot<C, Block> {
+	/// Creates a new instance of the Ballot RPC helper
+	pub fn new(client: Arc<C>, executor: SubscriptionTaskExecutor) -> Self {
+		Self { client, executor, _marker: Default::default() }
+	}
+}
+
+/// Error codes of the ballot RPC
+pub enum Error {
+	/// The call to the runtime failed
+	RuntimeError,
+	/// The aadhaar id is not 16 bytes long
+	InvalidAadhaarId,
+}
+
+impl From<Error> for i32 {
+	fn from(e: Error) -> i32 {
+		match e {
+			Error::RuntimeError => 1,
+			Error::InvalidAadhaarId => 2,
+		}
+	}
+}
+
+/// Hex encode an aadhaar id for JSON
+fn hex_id(aadhaar_id: AadhaarId) -> Bytes {
+	Bytes(aadhaar_id.to_vec())
+}
+
+/// Decode a hex encoded aadhaar id
+pub fn parse_aadhaar_id(aadhaar_id: &Bytes) -> RpcResult<AadhaarId> {
+	AadhaarId::try_from(&aadhaar_id[..]).map_err(|_| {
+		CallError::Custom(ErrorObject::owned(
+			Error::InvalidAadhaarId.into(),
+			"Aadhaar id must be 16 bytes.",
+			Some(format!("{} bytes given", aadhaar_id.len())),
+		))
+		.into()
+	})
+}
+
+/// Report a failed runtime API call
+pub fn runtime_error(e: impl std::fmt::Debug) -> JsonRpseeError {
+	CallError::Custom(ErrorObject::owned(
+		Error::RuntimeError.into(),
+		"Unable to query the ballot runtime API.",
+		Some(format!("{:?}", e)),
+	))
+	.into()
+}
+
+/// Candidate, NOTA and referendum tallies and turnout of the session at the given block
+pub fn tally_at<C, Block>(client: &C, vote_index: VoteIndex, at: &BlockId<Block>) -> RpcResult<TallyView>
+where
+	Block: BlockT,
+	C: ProvideRuntimeApi<Block>,
+	C::Api: BallotRuntimeApi<Block, NumberFor<Block>, Block::Hash>,
+{
+	let api = client.runtime_api();
+	let candidates = api.candidates(at, vote_index).map_err(runtime_error)?;
+	let session = api.session(at, vote_index).map_err(runtime_error)?;
+	let result = api.results(at, vote_index).map_err(runtime_error)?;
+	// Archived sessions keep their turnout in the result
+	let turnout = session
+		.as_ref()
+		.map(|session| session.turnout)
+		.or_else(|| result.as_ref().map(|result| result.turnout))
+		.unwrap_or_default();
+
+	// Live NOTA and referendum tallies are served from api version 2 on
+	let live_tallies = api
+		.api_version::<dyn BallotRuntimeApi<Block, NumberFor<Block>, Block::Hash>>(at)
+		.map_err(runtime_error)?
+		.map_or(false, |version| version >= 2);
+	let nota = match &result {
+		Some(result) => result.nota,
+		None if live_tallies => api.nota_tally(at, vote_index).map_err(runtime_error)?,
+		None => Default::default(),
+	};
+	let is_referendum = session.map_or(false, |session| session.ballot_type == BallotType::Referendum);
+	let referendum = match result.and_then(|result| result.referendum) {
+		Some(outcome) => Some(outcome.into()),
+		None if is_referendum && live_tallies =>
+			Some(api.referendum_tally(at, vote_index).map_err(runtime_error)?.into()),
+		None => None,
+	};
+
+	Ok(TallyView {
+		vote_index,
+		candidates: candidates.into_iter().map(Into::into).collect(),
+		nota: nota.into(),
+		referendum,
+		turnout,
+	})
+}
+
+impl<C, Block> Ballot<C, Block>
+where
+	Block: BlockT,
+	C: HeaderBackend<Block>,
+{
+	/// Block to answer from, the best block when none is given
+	fn block_id(&self, at: Option<Block::Hash>) -> BlockId<Block> {
+		BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash))
+	}
+}
+
+#[async_trait]
+impl<C, Block> BallotApiServer<<Block as BlockT>::Hash, NumberFor<Block>, <Block as BlockT>::Hash>
+	for Ballot<C, Block>
+where
+	Block: BlockT,
+	C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + BlockchainEvents<Block> + Send + Sync + 'static,
+	C::Api: BallotRuntimeApi<Block, NumberFor<Block>, Block::Hash>,
+{
+	fn session(
+		&self,
+		vote_index: VoteIndex,
+		at: Option<Block::Hash>,
+	) -> RpcResult<Option<SessionView<NumberFor<Block>, Block::Hash>>> {
+		let api = self.client.runtime_api();
+		let session = api.session(&self.block_id(at), vote_index).map_err(runtime_error)?;
+
+		Ok(session.map(Into::into))
+	}
+
+	fn list_sessions(&self, at: Option<Block::Hash>) -> RpcResult<Vec<VoteIndex>> {
+		let api = self.client.runtime_api();
+		api.active_sessions(&self.block_id(at)).map_err(runtime_error)
+	}
+
+	fn tally(&self, vote_index: VoteIndex, at: Option<Block::Hash>) -> RpcResult<TallyView> {
+		tally_at(&*self.client, vote_index, &self.block_id(at))
+	}
+
+	fn has_voted(&self, vote_index: VoteIndex, aadhaar_id: Bytes, at: Option<Block::Hash>) -> RpcResult<bool> {
+		let aadhaar_id = parse_aadhaar_id(&aadhaar_id)?;
+		let api = self.client.runtime_api();
+		api.has_voted(&self.block_id(at), vote_index, aadhaar_id).map_err(runtime_error)
+	}
+
+	fn results(&self, vote_index: VoteIndex, at: Option<Block::Hash>) -> RpcResult<Option<ResultView>> {
+		let api = self.client.runtime_api();
+		let results = api.results(&self.block_id(at), vote_index).map_err(runtime_error)?;
+
+		Ok(results.map(Into::into))
+	}
+
+	fn subscribe_tally(
+		&self,
+		mut sink: SubscriptionSink,
+		vote_index: VoteIndex,
+		follow: Option<Follow>,
+	) -> SubscriptionResult {
+		let info = self.client.info();
+		let (start, blocks) = match follow.unwrap_or_default() {
+			Follow::Best => (
+				info.best_hash,
+				self.client
+					.import_notification_stream()
+					.filter(|notification| future::ready(notification.is_new_best))
+					.map(|notification| notification.hash)
+					.boxed(),
+			),
+			Follow::Finalized => (
+				info.finalized_hash,
+				self.client.finality_notification_stream().map(|notification| notification.hash).boxed(),
+			),
+		};
+
+		// Push the current tally, then only the tallies a block changed
+		let client = self.client.clone();
+		let mut last: Option<TallyView> = None;
+		let tallies = stream::once(future::ready(start)).chain(blocks).filter_map(move |hash| {
+			let changed = tally_at(&*client, vote_index, &BlockId::hash(hash))
+				.ok()
+				.filter(|tally| last.as_ref() != Some(tally));
+			if let Some(tally) = &changed {
+				last = Some(tally.clone());
+			}
+			future::ready(changed)
+		});
+
+		let fut = async move {
+			sink.pipe_from_stream(tallies).await;
+		};
+		self.executor.spawn("ballot-rpc-subscription", Some("rpc"), fut.boxed());
+
+		Ok(())
+	}
+}
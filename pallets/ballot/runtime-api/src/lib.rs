@@ -0,0 +1,49 @@
+//! Runtime API for querying the voting sessions of pallet-ballot
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::Codec;
+use pallet_aadhaar::types::AadhaarId;
+use pallet_ballot::types::{
+	Candidate, ElectionResult, ProposalInfo, ReferendumTally, SessionInfo, VoteCount, VoteIndex,
+};
+use sp_std::prelude::*;
+
+sp_api::decl_runtime_apis! {
+	/// Read access to voting sessions without decoding pallet storage
+	///
+	/// Bump the api version when a method changes and keep the old one under
+	/// `#[changed_in]` so clients can check the version with `has_api_with`
+	///
+	/// Version 2 added nota_tally, referendum_tally and proposal
+	#[api_version(2)]
+	pub trait BallotApi<BlockNumber, Hash>
+	where
+		BlockNumber: Codec,
+		Hash: Codec,
+	{
+		/// Overview of the session, None when it does not exist or was reset
+		fn session(vote_index: VoteIndex) -> Option<SessionInfo<BlockNumber, Hash>>;
+
+		/// Candidates of the session with their tallies, ordered by votes
+		fn candidates(vote_index: VoteIndex) -> Vec<Candidate>;
+
+		/// Whether the voter cast a ballot on the session
+		fn has_voted(vote_index: VoteIndex, aadhaar_id: AadhaarId) -> bool;
+
+		/// Sessions accepting ballots or counting them
+		fn active_sessions() -> Vec<VoteIndex>;
+
+		/// Result of a decided session
+		fn results(vote_index: VoteIndex) -> Option<ElectionResult>;
+
+		/// Votes for None of the Above on the session
+		fn nota_tally(vote_index: VoteIndex) -> VoteCount;
+
+		/// Yes, No and Abstain votes of a referendum
+		fn referendum_tally(vote_index: VoteIndex) -> ReferendumTally;
+
+		/// Proposal put to the voters of a referendum
+		fn proposal(vote_index: VoteIndex) -> Option<ProposalInfo<Hash>>;
+	}
+}
@@ -0,0 +1,63 @@
+use super::*;
+
+/// Queries served by the BallotApi runtime API
+///
+/// Clients read sessions through these instead of decoding the storage
+/// of the pallet. Archived sessions are answered from their result
+impl<T: Config> Pallet<T> {
+
+	/// Overview of the session, None when it does not exist or was reset
+	pub fn session_info(vote_index: VoteIndex) -> Option<SessionInfo<T::BlockNumber, T::Hash>> {
+		Sessions::<T>::get(vote_index).map(|config| SessionInfo {
+			vote_index,
+			state: VotingState::<T>::get(vote_index),
+			ballot_type: config.ballot_type,
+			commissioner: ChiefCommissioner::<T>::get(vote_index),
+			starts_at: config.starts_at,
+			ends_at: config.ends_at,
+			secret: config.secret,
+			majority: config.majority,
+			constituencies: config.constituencies.into_inner(),
+			electoral_roll: config.electoral_roll,
+			turnout: Turnout::<T>::get(vote_index),
+			parent: ParentSession::<T>::get(vote_index),
+			runoff: RunoffOf::<T>::get(vote_index),
+		})
+	}
+
+	/// Candidates of the session with their tallies, ordered by votes
+	pub fn candidate_tallies(vote_index: VoteIndex) -> Vec<Candidate> {
+		match Results::<T>::get(vote_index) {
+			Some(result) if VotingState::<T>::get(vote_index) == VoteState::Archived => result.standings,
+			_ => Self::standings(vote_index),
+		}
+	}
+
+	/// Check the voter cast a ballot on the session, always false once it is archived
+	pub fn has_voted(vote_index: VoteIndex, aadhaar_id: AadhaarId) -> bool {
+		Votes::<T>::contains_key(vote_index, aadhaar_id)
+	}
+
+	/// Proposal put to the voters of a referendum
+	pub fn proposal_info(vote_index: VoteIndex) -> Option<ProposalInfo<T::Hash>> {
+		Proposals::<T>::get(vote_index).map(|proposal| match proposal {
+			Proposal::Text(text) => ProposalInfo::Text(text.into_inner()),
+			Proposal::Hash(hash) => ProposalInfo::Hash(hash),
+		})
+	}
+
+	/// Sessions accepting ballots or counting them
+	pub fn active_sessions() -> Vec<VoteIndex> {
+		let mut sessions: Vec<VoteIndex> = VotingState::<T>::iter()
+			.filter(|(_, state)| {
+				matches!(
+					state,
+					VoteState::Voting | VoteState::Committing | VoteState::Revealing | VoteState::Tallying
+				)
+			})
+			.map(|(vote_index, _)| vote_index)
+			.collect();
+		sessions.sort();
+		sessions
+	}
+}
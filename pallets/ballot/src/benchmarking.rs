@@ -0,0 +1,183 @@
+//! Benchmarking setup for pallet-ballot
+
+use super::*;
+
+#[allow(unused)]
+use crate::Pallet as Ballot;
+use frame_benchmarking::{account, benchmarks, BenchmarkError};
+use frame_system::RawOrigin;
+
+const SEED: u32 = 0;
+/// Index of the benchmark voter's aadhaar id, clear of the candidates
+const VOTER: u32 = u32::MAX - 1;
+
+/// Aadhaar id of the benchmark account with the given index, never NOTA
+fn aadhaar_id(index: u32) -> AadhaarId {
+	let mut aadhaar_id = [1u8; 16];
+	aadhaar_id[..4].copy_from_slice(&index.to_le_bytes());
+	aadhaar_id
+}
+
+/// Register c candidate accounts with the aadhaar pallet
+fn register_candidates<T: Config>(c: u32) -> Result<Vec<T::AccountId>, BenchmarkError> {
+	(0..c)
+		.map(|index| {
+			let candidate: T::AccountId = account("candidate", index, SEED);
+			AadhaarPallet::<T>::do_register_aadhaar(&candidate, aadhaar_id(index))?;
+			Ok(candidate)
+		})
+		.collect()
+}
+
+/// Register the commissioner behind the commission origin, returns the origin and its account
+fn commissioner<T: Config>() -> Result<(T::Origin, T::AccountId), BenchmarkError> {
+	let origin = T::ElectionCommissionOrigin::successful_origin();
+	let commissioner = T::ElectionCommissionOrigin::try_origin(origin.clone())
+		.map_err(|_| BenchmarkError::Weightless)?;
+	AadhaarPallet::<T>::do_register_aadhaar(&commissioner, aadhaar_id(u32::MAX))?;
+	Ok((origin, commissioner))
+}
+
+/// Register the benchmark voter with the aadhaar pallet
+fn register_voter<T: Config>() -> Result<T::AccountId, BenchmarkError> {
+	let voter: T::AccountId = account("voter", 0, SEED);
+	AadhaarPallet::<T>::do_register_aadhaar(&voter, aadhaar_id(VOTER))?;
+	Ok(voter)
+}
+
+/// Open a session as a registered commissioner, returns the commission origin and the session
+fn open_session<T: Config>(config: SessionConfig<T>) -> Result<(T::Origin, VoteIndex), BenchmarkError> {
+	let (origin, commissioner) = commissioner::<T>()?;
+	let (_, vote_index) = Ballot::<T>::do_start_voting(&commissioner, config)?;
+	Ok((origin, vote_index))
+}
+
+/// Open a session with c candidates, each with a different number of votes
+fn session_with_candidates<T: Config>(c: u32, config: SessionConfig<T>) -> Result<(T::Origin, VoteIndex), BenchmarkError> {
+	let (origin, vote_index) = open_session::<T>(config)?;
+	let candidates = register_candidates::<T>(c)?;
+	Ballot::<T>::do_add_candidates(vote_index, &candidates)?;
+	for index in 0..c {
+		Candidates::<T>::mutate(vote_index, aadhaar_id(index), |candidate| {
+			candidate.vote_count = index.saturating_add(1).into();
+		});
+	}
+	Ok((origin, vote_index))
+}
+
+/// Put the benchmark voter on an electoral roll d levels deep and fill the voter tree so
+/// recording the ballot hashes up d levels, returns the voter's roll proof
+fn deep_voter_set<T: Config>(vote_index: VoteIndex, d: u32) -> Result<RollProof<T>, BenchmarkError> {
+	let proof: RollProof<T> = vec![T::Hash::default(); d as usize]
+		.try_into()
+		.map_err(|_| BenchmarkError::Weightless)?;
+	let root = proof
+		.iter()
+		.fold(Ballot::<T>::roll_leaf(&aadhaar_id(VOTER)), |node, sibling| Ballot::<T>::roll_node(node, *sibling));
+	Sessions::<T>::mutate(vote_index, |config| {
+		if let Some(config) = config {
+			config.electoral_roll = Some(root);
+		}
+	});
+	VoterTrees::<T>::insert(vote_index, VoterTree { branch: proof.clone(), count: ((1u64 << d) - 1) as u32 });
+	Ok(proof)
+}
+
+benchmarks! {
+	start_voting {
+		let k in 0 .. T::MaxSessionConstituencies::get();
+		let (origin, _) = commissioner::<T>()?;
+		let constituencies: BoundedVec<_, _> = (0..k)
+			.map(|constituency_id| {
+				pallet_aadhaar::Constituencies::<T>::insert(constituency_id, BoundedVec::default());
+				constituency_id
+			})
+			.collect::<Vec<_>>()
+			.try_into()
+			.map_err(|_| BenchmarkError::Weightless)?;
+		// A scheduled secret ballot books every schedule it can
+		let now = frame_system::Pallet::<T>::block_number();
+		let config = SessionConfig {
+			secret: true,
+			starts_at: Some(now.saturating_add(1u32.into())),
+			ends_at: Some(now.saturating_add(2u32.into())),
+			reveal_ends_at: Some(now.saturating_add(3u32.into())),
+			constituencies,
+			..Default::default()
+		};
+		let vote_index = CurrentVoteIndex::<T>::get();
+	}: _<T::Origin>(origin, config)
+	verify {
+		assert!(Sessions::<T>::contains_key(vote_index));
+	}
+
+	add_candidates {
+		let c in 1 .. T::MaxCandidatesPerSession::get();
+		let (origin, vote_index) = open_session::<T>(Default::default())?;
+		let candidates: BoundedVec<_, _> = register_candidates::<T>(c)?
+			.try_into()
+			.map_err(|_| BenchmarkError::Weightless)?;
+	}: _<T::Origin>(origin, vote_index, candidates)
+	verify {
+		assert_eq!(CandidateCount::<T>::get(vote_index), c);
+	}
+
+	stop_voting {
+		let c in 1 .. T::MaxCandidatesPerSession::get();
+		let (origin, vote_index) = session_with_candidates::<T>(c, Default::default())?;
+	}: _<T::Origin>(origin, vote_index)
+	verify {
+		assert!(Results::<T>::contains_key(vote_index));
+	}
+
+	reset_voting {
+		let c in 1 .. T::MaxCandidatesPerSession::get();
+		let (origin, vote_index) = session_with_candidates::<T>(c, Default::default())?;
+	}: _<T::Origin>(origin, vote_index)
+	verify {
+		assert_eq!(Candidates::<T>::iter_prefix(vote_index).count(), 0);
+	}
+
+	vote {
+		let d in 0 .. T::MaxRollProofDepth::get();
+		// Recasting sessions also read the voter's earlier choice
+		let config = SessionConfig { allow_recast: true, ..Default::default() };
+		let (_, vote_index) = session_with_candidates::<T>(1, config)?;
+		let voter = register_voter::<T>()?;
+		let proof = deep_voter_set::<T>(vote_index, d)?;
+	}: _(RawOrigin::Signed(voter), vote_index, aadhaar_id(0), proof)
+	verify {
+		assert!(Votes::<T>::contains_key(vote_index, aadhaar_id(VOTER)));
+	}
+
+	cast_ballot {
+		let c in 1 .. T::MaxCandidatesPerSession::get();
+		let d in 0 .. T::MaxRollProofDepth::get();
+		let config = SessionConfig { ballot_type: BallotType::Approval, ..Default::default() };
+		let (_, vote_index) = session_with_candidates::<T>(c, config)?;
+		let voter = register_voter::<T>()?;
+		let proof = deep_voter_set::<T>(vote_index, d)?;
+		let approved: BoundedVec<_, _> = (0..c)
+			.map(aadhaar_id)
+			.collect::<Vec<_>>()
+			.try_into()
+			.map_err(|_| BenchmarkError::Weightless)?;
+	}: _(RawOrigin::Signed(voter), vote_index, BallotChoice::Approval(approved), proof)
+	verify {
+		assert_eq!(Turnout::<T>::get(vote_index), 1);
+	}
+
+	commit_vote {
+		let d in 0 .. T::MaxRollProofDepth::get();
+		let config = SessionConfig { secret: true, ..Default::default() };
+		let (_, vote_index) = session_with_candidates::<T>(1, config)?;
+		let voter = register_voter::<T>()?;
+		let proof = deep_voter_set::<T>(vote_index, d)?;
+		let commitment = Ballot::<T>::vote_commitment(vote_index, &aadhaar_id(VOTER), &aadhaar_id(0), &Default::default());
+	}: _(RawOrigin::Signed(voter), vote_index, commitment, proof)
+	verify {
+		assert!(Commitments::<T>::contains_key(vote_index, aadhaar_id(VOTER)));
+	}
+
+	impl_benchmark_test_suite!(Ballot, crate::mock::new_test_ext(), crate::mock::Test);
+}
@@ -1,22 +1,40 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
-/// This pallet allows to cast vote on a voting session
-/// Anyone with Election Commission Origin can start a 
-/// voting session by calling start_voting and then
-/// add candidates. Then users can vote for candidates
+//! This pallet allows to cast vote on a voting session
+//! Anyone with Election Commission Origin can start a
+//! voting session by calling start_voting and then
+//! add candidates. Then users can vote for candidates
+//!
+//! Sessions can be secret, scheduled, ranked, approval, score or
+//! referendum ballots. See the docs of SessionConfig and the calls
 
 
 use frame_support::{
     codec::{ Decode, Encode, MaxEncodedLen },
+    traits::{
+        BalanceStatus, Currency, LockIdentifier, LockableCurrency, OnUnbalanced, ReservableCurrency, WithdrawReasons,
+    },
 };
 use scale_info::TypeInfo;
-use pallet_aadhaar::types::AadhaarId;
+use pallet_aadhaar::types::{AadhaarId, ConstituencyId};
 use frame_support::pallet_prelude::*;
 use frame_system::pallet_prelude::*;
+use sp_runtime::{traits::{Hash, One, Zero}, PerThing, Perbill, SaturatedConversion};
 use sp_std::prelude::*;
 use crate::types::*;
+pub use weights::WeightInfo;
 
-mod types;
+pub mod types;
+mod ranked;
+mod merkle;
+mod tiebreak;
+mod purge;
+mod api;
+pub mod migrations;
+pub mod weights;
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
 
 #[cfg(test)]
 mod mock;
@@ -32,8 +50,15 @@ pub mod pallet {
 
 	pub type AadhaarPallet<T> = pallet_aadhaar::Pallet::<T>;
 
+	/// The current storage version
+	const STORAGE_VERSION: StorageVersion = StorageVersion::new(2);
+
+	/// Lock held on balances backing weighted votes
+	const BALLOT_LOCK_ID: LockIdentifier = *b"ballot  ";
+
 	#[pallet::pallet]
 	#[pallet::generate_store(pub(super) trait Store)]
+	#[pallet::storage_version(STORAGE_VERSION)]
 	pub struct Pallet<T>(_);
 
 
@@ -44,6 +69,75 @@ pub mod pallet {
 
 		/// Origin who manage voting session
 		type ElectionCommissionOrigin: EnsureOrigin<Self::Origin, Success = Self::AccountId>;
+
+		/// Maximum number of sessions that can be scheduled to open or close in the same block
+		#[pallet::constant]
+		type MaxScheduledPerBlock: Get<u32>;
+
+		/// Maximum number of candidates a voter can rank on a ranked ballot
+		#[pallet::constant]
+		type MaxBallotChoices: Get<u32>;
+
+		/// Maximum number of ranked ballots counted in a block
+		#[pallet::constant]
+		type MaxTallyStepsPerBlock: Get<u32>;
+
+		/// Maximum length in bytes of a referendum proposal text
+		#[pallet::constant]
+		type MaxProposalLength: Get<u32>;
+
+		/// Maximum number of constituencies a session can be bound to
+		#[pallet::constant]
+		type MaxSessionConstituencies: Get<u32>;
+
+		/// Maximum depth of the electoral roll Merkle tree
+		#[pallet::constant]
+		type MaxRollProofDepth: Get<u32>;
+
+		/// Currency nomination deposits are reserved and vote weights are locked in
+		type Currency: ReservableCurrency<Self::AccountId> + LockableCurrency<Self::AccountId, Moment = Self::BlockNumber>;
+
+		/// Deposit reserved when self-nominating for a session
+		#[pallet::constant]
+		type NominationDeposit: Get<BalanceOf<Self>>;
+
+		/// Share of the votes a candidate needs to get the nomination deposit back
+		#[pallet::constant]
+		type DepositReturnThreshold: Get<Perbill>;
+
+		/// Handler for slashed nomination deposits
+		type DepositSlash: OnUnbalanced<NegativeImbalanceOf<Self>>;
+
+		/// Maximum length in bytes of candidate and party names
+		#[pallet::constant]
+		type MaxNameLength: Get<u32>;
+
+		/// Maximum length in bytes of a manifesto content identifier
+		#[pallet::constant]
+		type MaxManifestoLength: Get<u32>;
+
+		/// Blocks commission members have to commit, and then to reveal, lot secrets
+		#[pallet::constant]
+		type TieBreakDelay: Get<Self::BlockNumber>;
+
+		/// Maximum number of voters a delegate can vote for
+		#[pallet::constant]
+		type MaxDelegatorsPerDelegate: Get<u32>;
+
+		/// Blocks a decided session keeps its per-voter data before it is archived
+		#[pallet::constant]
+		type RetentionPeriod: Get<Self::BlockNumber>;
+
+		/// Maximum number of candidates, including pending nominations, of a session
+		#[pallet::constant]
+		type MaxCandidatesPerSession: Get<u32>;
+
+		/// Blocks a repoll after NOTA won takes candidates and nominations before it opens
+		#[pallet::constant]
+		type RepollDelay: Get<Self::BlockNumber>;
+
+		/// Weight information for extrinsics in this pallet
+		type WeightInfo: WeightInfo;
 	}
 
 	/// Stores the current index of voting session
@@ -60,7 +154,85 @@ pub mod pallet {
 	pub type ChiefCommissioner<T> = StorageMap<_, Blake2_128Concat, VoteIndex, AadhaarId>;
 
 
-	/// It stores voting state (Idle, Voting and Ended) of given vote index
+	/// It stores the options the given voting session was started with
+	#[pallet::storage]
+	#[pallet::getter(fn session)]
+	pub type Sessions<T: Config> = StorageMap<_, Blake2_128Concat, VoteIndex, SessionConfig<T>>;
+
+
+	/// It stores the voting sessions which are scheduled to open at the given block
+	#[pallet::storage]
+	#[pallet::getter(fn scheduled_starts)]
+	pub type ScheduledStarts<T: Config> = StorageMap<_, Blake2_128Concat, T::BlockNumber, BoundedVec<VoteIndex, T::MaxScheduledPerBlock>, ValueQuery>;
+
+
+	/// It stores the voting sessions which are scheduled to close at the given block
+	#[pallet::storage]
+	#[pallet::getter(fn scheduled_ends)]
+	pub type ScheduledEnds<T: Config> = StorageMap<_, Blake2_128Concat, T::BlockNumber, BoundedVec<VoteIndex, T::MaxScheduledPerBlock>, ValueQuery>;
+
+
+	/// It stores the runoff session started when the given session had no majority winner
+	#[pallet::storage]
+	#[pallet::getter(fn runoff)]
+	pub type RunoffOf<T> = StorageMap<_, Blake2_128Concat, VoteIndex, VoteIndex>;
+
+
+	/// It stores the session a runoff session was started from
+	#[pallet::storage]
+	#[pallet::getter(fn parent_session)]
+	pub type ParentSession<T> = StorageMap<_, Blake2_128Concat, VoteIndex, VoteIndex>;
+
+
+	/// It stores the ranked ballots of a ranked choice session by ballot number
+	#[pallet::storage]
+	#[pallet::getter(fn ranked_ballots)]
+	pub type RankedBallots<T: Config> = StorageDoubleMap<_, Blake2_128Concat, VoteIndex, Blake2_128Concat, u32, BoundedVec<AadhaarId, T::MaxBallotChoices>>;
+
+
+	/// It stores the number of ranked ballots cast on a ranked choice session
+	#[pallet::storage]
+	#[pallet::getter(fn ranked_ballot_count)]
+	pub type RankedBallotCount<T> = StorageMap<_, Blake2_128Concat, VoteIndex, u32, ValueQuery>;
+
+
+	/// It stores the progress of the instant-runoff count of a ranked choice session
+	#[pallet::storage]
+	#[pallet::getter(fn tally_progress)]
+	pub type TallyProgress<T> = StorageMap<_, Blake2_128Concat, VoteIndex, RankedTally>;
+
+
+	/// It stores the votes of the remaining candidates in the current instant-runoff round
+	#[pallet::storage]
+	#[pallet::getter(fn round_counts)]
+	pub type RoundCounts<T> = StorageDoubleMap<_, Blake2_128Concat, VoteIndex, Blake2_128Concat, AadhaarId, u32, ValueQuery>;
+
+
+	/// It stores the round in which a candidate was eliminated from a ranked choice session
+	#[pallet::storage]
+	#[pallet::getter(fn eliminated)]
+	pub type Eliminated<T> = StorageDoubleMap<_, Blake2_128Concat, VoteIndex, Blake2_128Concat, AadhaarId, u32>;
+
+
+	/// It stores the outcome of every instant-runoff round of a ranked choice session
+	#[pallet::storage]
+	#[pallet::getter(fn elimination_log)]
+	pub type EliminationLog<T> = StorageDoubleMap<_, Blake2_128Concat, VoteIndex, Blake2_128Concat, u32, EliminationRound>;
+
+
+	/// It stores the proposal put to the voters of a referendum
+	#[pallet::storage]
+	#[pallet::getter(fn proposal)]
+	pub type Proposals<T: Config> = StorageMap<_, Blake2_128Concat, VoteIndex, Proposal<T>>;
+
+
+	/// It stores the votes cast on each option of a referendum
+	#[pallet::storage]
+	#[pallet::getter(fn referendum_tally)]
+	pub type ReferendumTallies<T> = StorageMap<_, Blake2_128Concat, VoteIndex, ReferendumTally, ValueQuery>;
+
+
+	/// It stores voting state (Idle, Voting, Ended, Committing, Revealing and Tallying) of given vote index
 	#[pallet::type_value]
 	pub fn StateDefault() -> VoteState { VoteState::Idle }
 	#[pallet::storage]
@@ -74,26 +246,241 @@ pub mod pallet {
 	pub type Candidates<T> = StorageDoubleMap<_, Blake2_128Concat, VoteIndex, Blake2_128Concat, AadhaarId, Candidate, ValueQuery>;
 
 
+	/// It stores the number of candidates and pending nominations of the given voting session
+	#[pallet::storage]
+	#[pallet::getter(fn candidate_count)]
+	pub type CandidateCount<T> = StorageMap<_, Blake2_128Concat, VoteIndex, u32, ValueQuery>;
+
+
+	/// It stores the votes for None of the Above in the given voting session
+	#[pallet::storage]
+	#[pallet::getter(fn nota_tally)]
+	pub type NotaTallies<T> = StorageMap<_, Blake2_128Concat, VoteIndex, VoteCount, ValueQuery>;
+
+
+	/// It stores the number of ballots cast in the given voting session
+	#[pallet::storage]
+	#[pallet::getter(fn turnout)]
+	pub type Turnout<T> = StorageMap<_, Blake2_128Concat, VoteIndex, u32, ValueQuery>;
+
+
+	/// It stores the outcome of a decided voting session
+	#[pallet::storage]
+	#[pallet::unbounded]
+	#[pallet::getter(fn results)]
+	pub type Results<T> = StorageMap<_, Blake2_128Concat, VoteIndex, ElectionResult>;
+
+
+	/// It stores how far the voter data of a reset session has been removed
+	#[pallet::storage]
+	#[pallet::unbounded]
+	#[pallet::getter(fn purge_progress)]
+	pub type PurgeCursors<T> = StorageMap<_, Blake2_128Concat, VoteIndex, PurgeProgress>;
+
+
+	/// It stores ties waiting for the commission or a lot to pick the winner
+	#[pallet::storage]
+	#[pallet::unbounded]
+	#[pallet::getter(fn pending_tie_break)]
+	pub type PendingTieBreaks<T: Config> = StorageMap<_, Blake2_128Concat, VoteIndex, PendingTieBreak<T>>;
+
+
+	/// It stores the sessions whose lot is drawn at the given block
+	#[pallet::storage]
+	pub type ScheduledDraws<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::BlockNumber, BoundedVec<VoteIndex, T::MaxScheduledPerBlock>, ValueQuery>;
+
+
+	/// It stores the decided sessions which are archived at the given block
+	#[pallet::storage]
+	pub type ScheduledArchives<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::BlockNumber, BoundedVec<VoteIndex, T::MaxScheduledPerBlock>, ValueQuery>;
+
+
+	/// It stores the Merkle tree of the voters of the given voting session until it is archived
+	#[pallet::storage]
+	pub type VoterTrees<T: Config> = StorageMap<_, Blake2_128Concat, VoteIndex, VoterTree<T>, ValueQuery>;
+
+
+	/// It stores the Merkle root of the voters of the given archived voting session
+	#[pallet::storage]
+	#[pallet::getter(fn voter_root)]
+	pub type VoterRoots<T: Config> = StorageMap<_, Blake2_128Concat, VoteIndex, T::Hash>;
+
+
+	/// It stores the lot secret commitment of a commission member
+	#[pallet::storage]
+	pub type TieSeedCommitments<T: Config> = StorageDoubleMap<_, Blake2_128Concat, VoteIndex, Blake2_128Concat, T::AccountId, T::Hash>;
+
+
+	/// It stores how the tie of the given voting session was broken
+	#[pallet::storage]
+	#[pallet::getter(fn tie_break_record)]
+	pub type TieBreakRecords<T: Config> = StorageMap<_, Blake2_128Concat, VoteIndex, TieBreakRecord<T>>;
+
+
+	/// It stores the candidate a voter currently backs on a session which allows recasting
+	#[pallet::storage]
+	#[pallet::getter(fn voted_for)]
+	pub type VotedFor<T> = StorageDoubleMap<_, Blake2_128Concat, VoteIndex, Blake2_128Concat, AadhaarId, AadhaarId>;
+
+
+	/// It stores the voting delegate registered by an aadhaar holder
+	#[pallet::storage]
+	#[pallet::getter(fn delegation)]
+	pub type Delegations<T: Config> = StorageMap<_, Blake2_128Concat, AadhaarId, Delegation<T>>;
+
+
+	/// It stores the aadhaar holders a delegate votes for
+	#[pallet::storage]
+	#[pallet::getter(fn delegators_of)]
+	pub type DelegatorsOf<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, BoundedVec<AadhaarId, T::MaxDelegatorsPerDelegate>, ValueQuery>;
+
+
+	/// It stores the balance an account locked to weight its vote on a session
+	#[pallet::storage]
+	#[pallet::getter(fn vote_lock)]
+	pub type VoteLocks<T: Config> = StorageDoubleMap<_, Blake2_128Concat, T::AccountId, Blake2_128Concat, VoteIndex, BalanceOf<T>>;
+
+
+	/// It stores the account whose balance backs a voter's weight on a session, it stays
+	/// locked there when the aadhaar is rotated to another account
+	#[pallet::storage]
+	#[pallet::getter(fn lock_funder)]
+	pub type LockFunders<T: Config> = StorageDoubleMap<_, Blake2_128Concat, AadhaarId, Blake2_128Concat, VoteIndex, T::AccountId>;
+
+
+	/// It stores the vote weights the commission set for a session
+	#[pallet::storage]
+	#[pallet::getter(fn vote_weight_of)]
+	pub type VoteWeights<T> = StorageDoubleMap<_, Blake2_128Concat, VoteIndex, Blake2_128Concat, AadhaarId, VoteCount, ValueQuery>;
+
+
 	/// It stores if votes were casted on an voting session by a voter id
 	#[pallet::storage]
 	#[pallet::getter(fn votes)]
 	pub type Votes<T> = StorageDoubleMap<_, Blake2_128Concat, VoteIndex, Blake2_128Concat, AadhaarId, bool, ValueQuery>;
 
 
+	/// It stores the parties registered by the election commission
+	#[pallet::storage]
+	#[pallet::getter(fn party)]
+	pub type Parties<T: Config> = StorageMap<_, Blake2_128Concat, PartyId, Party<T>>;
+
+
+	/// It stores the profile shown for a candidate of a voting session
+	#[pallet::storage]
+	#[pallet::getter(fn candidate_profile)]
+	pub type CandidateProfiles<T: Config> = StorageDoubleMap<_, Blake2_128Concat, VoteIndex, Blake2_128Concat, AadhaarId, CandidateProfile<T>>;
+
+
+	/// It stores the nominations and reserved deposits of a voting session
+	#[pallet::storage]
+	#[pallet::getter(fn nominations)]
+	pub type Nominations<T: Config> = StorageDoubleMap<_, Blake2_128Concat, VoteIndex, Blake2_128Concat, AadhaarId, Nomination<T>>;
+
+
+	/// It stores the unrevealed vote commitment of a voter on a secret ballot session
+	#[pallet::storage]
+	#[pallet::getter(fn commitments)]
+	pub type Commitments<T: Config> = StorageDoubleMap<_, Blake2_128Concat, VoteIndex, Blake2_128Concat, AadhaarId, T::Hash>;
+
+
+	/// It stores how many commitments were made and revealed on a secret ballot session
+	#[pallet::storage]
+	#[pallet::getter(fn secret_tally)]
+	pub type SecretTallies<T> = StorageMap<_, Blake2_128Concat, VoteIndex, SecretTally, ValueQuery>;
+
+
 
 	#[pallet::event]
 	#[pallet::generate_deposit(pub(super) fn deposit_event)]
 	pub enum Event<T: Config> {
 		/// Voting started by Aadhaar Id
 		VotingStarted { aadhaar_id: AadhaarId, vote_index: VoteIndex },
+		/// Voting scheduled by Aadhaar Id to open and close at the given blocks
+		VotingScheduled {
+			aadhaar_id: AadhaarId,
+			vote_index: VoteIndex,
+			starts_at: T::BlockNumber,
+			ends_at: Option<T::BlockNumber>,
+		},
 		/// Voting started by Aadhaar Id
 		VotingStopped { aadhaar_id: AadhaarId, vote_index: VoteIndex },
 		/// Voting started by Aadhaar Id
 		VotingReset { aadhaar_id: AadhaarId, vote_index: VoteIndex },
+		/// All voter data of a reset session was removed
+		SessionPurged { vote_index: VoteIndex },
+		/// Retention period passed, voter data is pruned and only the result and voter root kept
+		SessionArchived { vote_index: VoteIndex, voter_root: T::Hash },
 		/// Added Candidates
 		AddedCandidates { vote_index: VoteIndex, candidates: Vec<T::AccountId> },
 		/// Voted
-		Voted { vote_index: VoteIndex, candidate: AadhaarId }
+		Voted { vote_index: VoteIndex, candidate: AadhaarId },
+		/// Voter replaced their vote, only the last one is counted
+		VoteChanged { vote_index: VoteIndex, from: AadhaarId, to: AadhaarId },
+		/// Secret ballot moved from committing to revealing by Aadhaar Id
+		RevealStarted { aadhaar_id: AadhaarId, vote_index: VoteIndex },
+		/// Vote commitment submitted on a secret ballot
+		VoteCommitted { vote_index: VoteIndex },
+		/// Committed vote revealed and counted
+		VoteRevealed { vote_index: VoteIndex, candidate: AadhaarId },
+		/// Secret ballot closed with commitments that were never revealed
+		CommitmentsUnrevealed { vote_index: VoteIndex, count: u32 },
+		/// No candidate reached the majority and a runoff session was started
+		RunoffStarted { vote_index: VoteIndex, runoff_index: VoteIndex, candidates: Vec<AadhaarId> },
+		/// Final result of an election which started at first_round
+		ElectionDecided { vote_index: VoteIndex, first_round: VoteIndex, winner: Option<AadhaarId> },
+		/// Ballot cast on a session which takes more than a single candidate
+		BallotCast { vote_index: VoteIndex },
+		/// Candidate eliminated in an instant-runoff round
+		CandidateEliminated { vote_index: VoteIndex, round: u32, candidate: AadhaarId, votes: u32 },
+		/// Instant-runoff count finished after the given number of rounds
+		TallyCompleted { vote_index: VoteIndex, rounds: u32 },
+		/// Referendum ended and the proposal was approved or rejected
+		ReferendumDecided { vote_index: VoteIndex, approved: bool, tally: ReferendumTally },
+		/// Registered aadhaar holder nominated themselves and reserved the deposit
+		Nominated { vote_index: VoteIndex, candidate: AadhaarId, deposit: BalanceOf<T> },
+		/// Nomination accepted by the commission, the nominee is now a candidate
+		NominationAccepted { vote_index: VoteIndex, candidate: AadhaarId },
+		/// Nomination rejected by the commission and the deposit returned
+		NominationRejected { vote_index: VoteIndex, candidate: AadhaarId },
+		/// Nominee withdrew before polling opened and the deposit was returned
+		NominationWithdrawn { vote_index: VoteIndex, candidate: AadhaarId },
+		/// Candidate reached the vote-share threshold and got the deposit back
+		DepositReturned { vote_index: VoteIndex, candidate: AadhaarId, amount: BalanceOf<T> },
+		/// Candidate missed the vote-share threshold and the deposit was slashed
+		DepositSlashed { vote_index: VoteIndex, candidate: AadhaarId, amount: BalanceOf<T> },
+		/// Party added to the registry
+		PartyRegistered { party_id: PartyId },
+		/// Profile of a candidate set or updated
+		CandidateProfileSet { vote_index: VoteIndex, candidate: AadhaarId },
+		/// None of the Above polled more votes than every candidate
+		NotaWon { vote_index: VoteIndex, votes: VoteCount },
+		/// NOTA won and a new session was started for the seat
+		RepollStarted { vote_index: VoteIndex, repoll_index: VoteIndex },
+		/// Outcome of the session written to Results
+		ResultDeclared { vote_index: VoteIndex, winners: Vec<AadhaarId>, tied: bool },
+		/// Candidates share the lead and wait for the commission or a lot
+		TieBreakPending { vote_index: VoteIndex, method: TieBreak, candidates: Vec<AadhaarId> },
+		/// Tie broken, seed is set when the winner was drawn by lot
+		TieBroken { vote_index: VoteIndex, winner: Option<AadhaarId>, seed: Option<T::Hash> },
+		/// Aadhaar holder registered a voting delegate
+		VoteDelegated {
+			delegator: AadhaarId,
+			delegate: T::AccountId,
+			scope: DelegationScope,
+			expires_at: T::BlockNumber,
+		},
+		/// Aadhaar holder revoked their voting delegate
+		DelegationRevoked { delegator: AadhaarId, delegate: T::AccountId },
+		/// Balance locked to weight a vote until the session ends
+		VoteWeightLocked { vote_index: VoteIndex, who: T::AccountId, amount: BalanceOf<T> },
+		/// Balance backing a weighted vote unlocked after the session ended
+		VoteWeightUnlocked { vote_index: VoteIndex, who: T::AccountId },
+		/// Commission set the vote weights of a session
+		VoteWeightsSet { vote_index: VoteIndex, count: u32 },
 	}
 
 	#[pallet::error]
@@ -104,59 +491,249 @@ pub mod pallet {
 		VoteAlreadyCast,
 		/// Voting Not Active
 		VotingNotActive,
+		/// Caller has no registered aadhaar
+		VoterNotRegistered,
+		/// Candidate not found in voting session
+		CandidateNotFound,
+		/// Voting session is not accepting vote commitments
+		CommitPhaseNotActive,
+		/// Voting session is not accepting reveals
+		RevealPhaseNotActive,
+		/// No commitment to reveal
+		CommitmentNotFound,
+		/// Revealed vote does not match the commitment
+		CommitmentMismatch,
+		/// Voting window is in the past or closes before it opens
+		InvalidSchedule,
+		/// Too many sessions scheduled to open or close in the same block
+		TooManyScheduled,
+		/// A runoff needs at least two candidates
+		InvalidRunoffSize,
+		/// Session options can not be combined with its ballot type
+		UnsupportedBallotConfig,
+		/// Ballot does not match the ballot type of the session
+		BallotTypeMismatch,
+		/// Ballot lists no candidates
+		EmptyBallot,
+		/// Ballot lists the same candidate more than once
+		DuplicateChoice,
+		/// Score is above the maximum score of the session
+		ScoreOutOfRange,
+		/// Voter is not assigned to any constituency of the session
+		VoterNotInElectorate,
+		/// Constituency is not in the aadhaar registry
+		ConstituencyNotFound,
+		/// Proof does not show the voter is on the session's electoral roll
+		NotOnElectoralRoll,
+		/// Candidate account has no registered aadhaar
+		CandidateNotRegistered,
+		/// Nominations are only taken before a scheduled session opens
+		NominationsClosed,
+		/// Aadhaar already nominated or standing as a candidate
+		AlreadyNominated,
+		/// Nomination not found in voting session
+		NominationNotFound,
+		/// Nomination was already accepted
+		NominationAlreadyAccepted,
+		/// Party already in the registry
+		PartyAlreadyExists,
+		/// Party not found in the registry
+		PartyNotFound,
+		/// Candidate profiles cannot change once polling opens
+		ProfileLocked,
+		/// Candidates cannot be added once polling opens
+		CandidatesLocked,
+		/// Aadhaar id is reserved for None of the Above
+		ReservedCandidateId,
+		/// Session was decided and its result can no longer change
+		ResultAlreadyDeclared,
+		/// Session has no tie waiting to be broken
+		NoTieToBreak,
+		/// Tie is broken by another method
+		TieBreakMethodMismatch,
+		/// Candidate is not one of the tied candidates
+		NotATiedCandidate,
+		/// Lot secrets are no longer taken
+		TieSeedCommitClosed,
+		/// Lot secrets are not being revealed
+		TieSeedRevealNotActive,
+		/// Delegation expires in the past or names the delegator as delegate
+		InvalidDelegation,
+		/// Voter has no voting delegate
+		DelegationNotFound,
+		/// Caller is not the voter's delegate for this session, or the delegation expired
+		NotDelegate,
+		/// Delegate already votes for the maximum number of voters
+		TooManyDelegators,
+		/// Session does not weight votes this way
+		WeightSourceMismatch,
+		/// Voter has no vote weight on the session
+		NoVoteWeight,
+		/// Vote weight was already locked for the session
+		VoteWeightAlreadyLocked,
+		/// Locked amount is zero or above the free balance
+		InvalidLockAmount,
+		/// Vote weight stays locked until the session ends
+		SessionNotEnded,
+		/// Vote weights cannot change once polling opens
+		VoteWeightsLocked,
+		/// Session already has the maximum number of candidates
+		TooManyCandidates,
+		/// Session was reset and is being purged
+		SessionPurging,
+	}
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn on_runtime_upgrade() -> Weight {
+			migrations::v1::migrate::<T>().saturating_add(migrations::v2::migrate::<T>())
+		}
+
+		/// Remove voter data of reset sessions with the weight left in the block
+		fn on_idle(_now: T::BlockNumber, remaining_weight: Weight) -> Weight {
+			Self::purge_sessions(remaining_weight)
+		}
+
+		/// Open and close the voting sessions scheduled for this block
+		fn on_initialize(now: T::BlockNumber) -> Weight {
+			let starts = ScheduledStarts::<T>::take(now);
+			let ends = ScheduledEnds::<T>::take(now);
+
+			for vote_index in starts.iter() {
+				Self::open_scheduled_session(*vote_index);
+			}
+			for vote_index in ends.iter() {
+				Self::close_scheduled_session(*vote_index, now);
+			}
+			let draws = ScheduledDraws::<T>::take(now);
+			for vote_index in draws.iter() {
+				Self::draw_lot(*vote_index);
+			}
+			let archives = ScheduledArchives::<T>::take(now);
+			for vote_index in archives.iter() {
+				Self::archive_session(*vote_index);
+			}
+
+			// Count ranked ballots of sessions which stopped voting
+			let mut budget = T::MaxTallyStepsPerBlock::get();
+			let mut counted: Weight = 0;
+			let mut tallied: Weight = 0;
+			for vote_index in TallyProgress::<T>::iter_keys().collect::<Vec<_>>() {
+				if budget == 0 {
+					break
+				}
+				let steps = Self::tally_ranked(vote_index, budget).max(1);
+				budget = budget.saturating_sub(steps);
+				counted = counted.saturating_add(steps.into());
+				tallied = tallied.saturating_add(1);
+			}
+
+			let db_weight = T::DbWeight::get();
+			let max_candidates = T::MaxCandidatesPerSession::get();
+			// Closing a session may conclude it, which ranks and settles every candidate
+			let conclude = T::WeightInfo::stop_voting(max_candidates);
+			// Archiving clears the candidate tallies of the session
+			let archive = db_weight.reads_writes(4, 4).saturating_add(db_weight.writes(max_candidates.into()));
+			// A tally step may finish the round, reading every candidate, and conclude the session
+			let round = db_weight.reads(max_candidates.saturating_mul(2).into()).saturating_add(conclude);
+			db_weight
+				.reads_writes(4, 4)
+				.saturating_add(db_weight.reads_writes(3, 2).saturating_mul(starts.len() as Weight))
+				.saturating_add(db_weight.reads_writes(3, 4).saturating_mul(draws.len() as Weight))
+				.saturating_add(conclude.saturating_mul(ends.len() as Weight))
+				.saturating_add(archive.saturating_mul(archives.len() as Weight))
+				.saturating_add(round.saturating_mul(tallied))
+				.saturating_add(
+					db_weight
+						.reads_writes(T::MaxBallotChoices::get().saturating_add(2).into(), 1)
+						.saturating_mul(counted),
+				)
+		}
 	}
 
 	#[pallet::call]
 	impl<T: Config> Pallet<T> {
 
-		/// Start a voting session
-		#[pallet::weight(10_000 + T::DbWeight::get().writes(1))]
-		pub fn start_voting(origin: OriginFor<T>) -> DispatchResult {
+		/// Start a voting session, either immediately or on the given schedule
+		#[pallet::weight(T::WeightInfo::start_voting(config.constituencies.len() as u32))]
+		pub fn start_voting(origin: OriginFor<T>, config: SessionConfig<T>) -> DispatchResult {
 			// Check if origin is a from a validator
 			let account_id = T::ElectionCommissionOrigin::ensure_origin(origin)?;
 
-			// Create voting session and set status to Voting
-			let (aadhaar_id, vote_index) = Self::do_start_voting(&account_id)?;
+			// Referendums are started with a proposal through start_referendum
+			ensure!(config.ballot_type != BallotType::Referendum, Error::<T>::UnsupportedBallotConfig);
+			let (starts_at, ends_at) = (config.starts_at, config.ends_at);
+
+			// Create voting session and set status to Voting or Committing, or schedule it
+			let (aadhaar_id, vote_index) = Self::do_start_voting(&account_id, config)?;
 
 			// Emit an event.
-			Self::deposit_event(Event::VotingStarted { aadhaar_id, vote_index });
+			Self::deposit_start_event(aadhaar_id, vote_index, starts_at, ends_at);
+
+			Ok(())
+		}
+
+		/// Start a referendum on a proposal, either immediately or on the given schedule
+		#[pallet::weight(T::WeightInfo::start_voting(config.constituencies.len() as u32).saturating_add(T::DbWeight::get().writes(1)))]
+		pub fn start_referendum(origin: OriginFor<T>, proposal: Proposal<T>, mut config: SessionConfig<T>) -> DispatchResult {
+			// Check if origin is a from a validator
+			let account_id = T::ElectionCommissionOrigin::ensure_origin(origin)?;
+
+			config.ballot_type = BallotType::Referendum;
+			let (starts_at, ends_at) = (config.starts_at, config.ends_at);
+
+			// Create voting session and store the proposal it decides on
+			let (aadhaar_id, vote_index) = Self::do_start_voting(&account_id, config)?;
+			Proposals::<T>::insert(vote_index, proposal);
+
+			// Emit an event.
+			Self::deposit_start_event(aadhaar_id, vote_index, starts_at, ends_at);
 
 			Ok(())
 		}
 
 		/// Stop a voting session. After winner can be chosen based on no of votes on candidates
-		#[pallet::weight(10_000 + T::DbWeight::get().writes(1))]
+		#[pallet::weight(T::WeightInfo::stop_voting(T::MaxCandidatesPerSession::get()))]
 		pub fn stop_voting(origin: OriginFor<T>, vote_index: VoteIndex) -> DispatchResult {
 			// Check if origin is a from a validator
 			let account_id = T::ElectionCommissionOrigin::ensure_origin(origin)?;
 
-			// Change voting state to ended
-			let (aadhaar_id, vote_index) = Self::do_stop_voting(&account_id, vote_index)?;
+			// Change voting state to ended, or move a secret ballot to its next phase
+			let (aadhaar_id, state) = Self::do_stop_voting(&account_id, vote_index)?;
 
 			// Emit an event.
-			Self::deposit_event(Event::VotingStarted { aadhaar_id, vote_index });
+			Self::deposit_phase_event(aadhaar_id, vote_index, &state);
+
+			// Declare the result or start a runoff
+			if state == VoteState::Ended {
+				Self::conclude_session(vote_index);
+			}
 
 			Ok(())
 		}
 
 		/// Remove voting data
-		#[pallet::weight(10_000 + T::DbWeight::get().writes(1))]
+		#[pallet::weight(T::WeightInfo::reset_voting(T::MaxCandidatesPerSession::get()))]
 		pub fn reset_voting(origin: OriginFor<T>, vote_index: VoteIndex) -> DispatchResult {
 			// Check if origin is a from a validator
 			let account_id = T::ElectionCommissionOrigin::ensure_origin(origin)?;
 
-			// Set voting state to Idle and remove candidates and votes
+			// Remove candidates and start purging the votes
 			let (aadhaar_id, vote_index) = Self::do_reset_voting(&account_id, vote_index)?;
 
 			// Emit an event.
-			Self::deposit_event(Event::VotingStarted { aadhaar_id, vote_index });
+			Self::deposit_event(Event::VotingReset { aadhaar_id, vote_index });
 
 			Ok(())
 		}
 
 		/// Add candidates to voting session
-		#[pallet::weight(10_000 + T::DbWeight::get().writes(1))]
-		pub fn add_candidates(origin: OriginFor<T>, vote_index: VoteIndex, candidates: Vec<T::AccountId>) -> DispatchResult {
+		#[pallet::weight(T::WeightInfo::add_candidates(candidates.len() as u32))]
+		pub fn add_candidates(
+			origin: OriginFor<T>,
+			vote_index: VoteIndex,
+			candidates: BoundedVec<T::AccountId, T::MaxCandidatesPerSession>,
+		) -> DispatchResult {
 			// Check if origin is a from a validator
 			T::ElectionCommissionOrigin::ensure_origin(origin)?;
 
@@ -164,22 +741,332 @@ pub mod pallet {
 			Self::do_add_candidates(vote_index, &candidates)?;
 
 			// Emit an event.
-			Self::deposit_event(Event::AddedCandidates { vote_index, candidates });
+			Self::deposit_event(Event::AddedCandidates { vote_index, candidates: candidates.into_inner() });
+
+			Ok(())
+		}
+
+		/// Nominate yourself as a candidate of a scheduled session by reserving the deposit
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(4, 2))]
+		pub fn nominate(origin: OriginFor<T>, vote_index: VoteIndex) -> DispatchResult {
+			let account_id = ensure_signed(origin)?;
+
+			// Reserve the deposit and record the nomination
+			let (candidate, deposit) = Self::do_nominate(&account_id, vote_index)?;
+
+			// Emit an event.
+			Self::deposit_event(Event::Nominated { vote_index, candidate, deposit });
+
+			Ok(())
+		}
+
+		/// Accept a nomination and add the nominee to the candidates of the session
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(2, 2))]
+		pub fn accept_nomination(origin: OriginFor<T>, vote_index: VoteIndex, candidate: AadhaarId) -> DispatchResult {
+			// Check if origin is a from a validator
+			T::ElectionCommissionOrigin::ensure_origin(origin)?;
+
+			Self::do_accept_nomination(vote_index, candidate)?;
+
+			// Emit an event.
+			Self::deposit_event(Event::NominationAccepted { vote_index, candidate });
+
+			Ok(())
+		}
+
+		/// Reject a pending nomination and return the deposit
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(2, 2))]
+		pub fn reject_nomination(origin: OriginFor<T>, vote_index: VoteIndex, candidate: AadhaarId) -> DispatchResult {
+			// Check if origin is a from a validator
+			T::ElectionCommissionOrigin::ensure_origin(origin)?;
+
+			let nomination = Nominations::<T>::get(vote_index, candidate).ok_or(Error::<T>::NominationNotFound)?;
+			ensure!(!nomination.accepted, Error::<T>::NominationAlreadyAccepted);
+			Self::do_remove_nomination(vote_index, candidate)?;
+
+			// Emit an event.
+			Self::deposit_event(Event::NominationRejected { vote_index, candidate });
+
+			Ok(())
+		}
+
+		/// Add a party to the registry
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(1, 1))]
+		pub fn register_party(origin: OriginFor<T>, party_id: PartyId, party: Party<T>) -> DispatchResult {
+			// Check if origin is a from a validator
+			T::ElectionCommissionOrigin::ensure_origin(origin)?;
+
+			ensure!(!Parties::<T>::contains_key(party_id), Error::<T>::PartyAlreadyExists);
+			Parties::<T>::insert(party_id, party);
+
+			// Emit an event.
+			Self::deposit_event(Event::PartyRegistered { party_id });
+
+			Ok(())
+		}
+
+		/// Set the profile of a candidate, either by the candidate or the commission
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(6, 1))]
+		pub fn set_candidate_profile(
+			origin: OriginFor<T>,
+			vote_index: VoteIndex,
+			candidate: AadhaarId,
+			profile: CandidateProfile<T>,
+		) -> DispatchResult {
+			// The commission may set any profile, candidates only their own
+			if let Err(origin) = T::ElectionCommissionOrigin::try_origin(origin) {
+				let account_id = ensure_signed(origin)?;
+				ensure!(AadhaarPallet::<T>::get_aadhaar_id(&account_id) == Some(candidate), DispatchError::BadOrigin);
+			}
+
+			Self::do_set_candidate_profile(vote_index, candidate, profile)?;
+
+			// Emit an event.
+			Self::deposit_event(Event::CandidateProfileSet { vote_index, candidate });
+
+			Ok(())
+		}
+
+		/// Pick the winner of a tie the session leaves to the commission
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(1, 2))]
+		pub fn break_tie(origin: OriginFor<T>, vote_index: VoteIndex, winner: AadhaarId) -> DispatchResult {
+			// Check if origin is a from a validator
+			T::ElectionCommissionOrigin::ensure_origin(origin)?;
+
+			let pending = PendingTieBreaks::<T>::get(vote_index).ok_or(Error::<T>::NoTieToBreak)?;
+			ensure!(pending.method == TieBreak::Commission, Error::<T>::TieBreakMethodMismatch);
+			ensure!(pending.tied.contains(&winner), Error::<T>::NotATiedCandidate);
+
+			PendingTieBreaks::<T>::remove(vote_index);
+			Self::complete_tie_break(vote_index, pending.method, None, Some(winner));
+
+			Ok(())
+		}
+
+		/// Commit to hash(secret) for the lot breaking a tie
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(1, 1))]
+		pub fn commit_tie_seed(origin: OriginFor<T>, vote_index: VoteIndex, commitment: T::Hash) -> DispatchResult {
+			let account_id = T::ElectionCommissionOrigin::ensure_origin(origin)?;
+
+			let pending = PendingTieBreaks::<T>::get(vote_index).ok_or(Error::<T>::NoTieToBreak)?;
+			ensure!(pending.method == TieBreak::Lot, Error::<T>::TieBreakMethodMismatch);
+			let now = frame_system::Pallet::<T>::block_number();
+			ensure!(now < pending.commit_until, Error::<T>::TieSeedCommitClosed);
+
+			TieSeedCommitments::<T>::insert(vote_index, account_id, commitment);
+
+			Ok(())
+		}
+
+		/// Reveal a committed lot secret, it is mixed into the seed the lot is drawn from
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(2, 2))]
+		pub fn reveal_tie_seed(origin: OriginFor<T>, vote_index: VoteIndex, secret: Salt) -> DispatchResult {
+			let account_id = T::ElectionCommissionOrigin::ensure_origin(origin)?;
+
+			Self::do_reveal_tie_seed(&account_id, vote_index, secret)
+		}
+
+		/// Withdraw your nomination before polling opens and get the deposit back
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(3, 3))]
+		pub fn withdraw_nomination(origin: OriginFor<T>, vote_index: VoteIndex) -> DispatchResult {
+			let account_id = ensure_signed(origin)?;
+			let candidate = AadhaarPallet::<T>::get_aadhaar_id(&account_id)
+				.ok_or(Error::<T>::VoterNotRegistered)?;
+
+			Self::do_remove_nomination(vote_index, candidate)?;
+
+			// Emit an event.
+			Self::deposit_event(Event::NominationWithdrawn { vote_index, candidate });
 
 			Ok(())
 		}
 
 		/// Vote on session by aadhaar registered users
-		#[pallet::weight(10_000 + T::DbWeight::get().writes(1))]
-		pub fn vote(origin: OriginFor<T>, vote_index: VoteIndex, candidate: AadhaarId) -> DispatchResult {
+		#[pallet::weight(T::WeightInfo::vote(T::MaxRollProofDepth::get()))]
+		pub fn vote(
+			origin: OriginFor<T>,
+			vote_index: VoteIndex,
+			candidate: AadhaarId,
+			proof: RollProof<T>,
+		) -> DispatchResult {
 			// Check if origin is a from a validator
 			let account_id = ensure_signed(origin)?;
 
+			let voter_id = AadhaarPallet::<T>::get_aadhaar_id(&account_id)
+				.ok_or(Error::<T>::VoterNotRegistered)?;
+
 			// Cast vote on a candidate on given voting session
-			Self::do_vote(&account_id, vote_index, candidate)?;
+			let replaced = Self::do_vote(voter_id, vote_index, candidate, &proof)?;
+
+			// Emit an event.
+			match replaced {
+				Some(from) => Self::deposit_event(Event::VoteChanged { vote_index, from, to: candidate }),
+				None => Self::deposit_event(Event::Voted { vote_index, candidate }),
+			}
+
+			Ok(())
+		}
+
+		/// Vote on behalf of a voter who registered the caller as their delegate
+		#[pallet::weight(T::WeightInfo::vote(T::MaxRollProofDepth::get()).saturating_add(T::DbWeight::get().reads(1)))]
+		pub fn vote_for(
+			origin: OriginFor<T>,
+			delegator: AadhaarId,
+			vote_index: VoteIndex,
+			candidate: AadhaarId,
+			proof: RollProof<T>,
+		) -> DispatchResult {
+			let account_id = ensure_signed(origin)?;
+
+			// Check the delegation covers this session and count the delegator's vote
+			Self::ensure_delegate(&account_id, &delegator, vote_index)?;
+			let replaced = Self::do_vote(delegator, vote_index, candidate, &proof)?;
+
+			// Emit an event.
+			match replaced {
+				Some(from) => Self::deposit_event(Event::VoteChanged { vote_index, from, to: candidate }),
+				None => Self::deposit_event(Event::Voted { vote_index, candidate }),
+			}
+
+			Ok(())
+		}
+
+		/// Register a delegate who may vote on your behalf until the given block
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(3, 3))]
+		pub fn delegate_vote(
+			origin: OriginFor<T>,
+			delegate: T::AccountId,
+			scope: DelegationScope,
+			expires_at: T::BlockNumber,
+		) -> DispatchResult {
+			let account_id = ensure_signed(origin)?;
+			let delegator = AadhaarPallet::<T>::get_aadhaar_id(&account_id)
+				.ok_or(Error::<T>::VoterNotRegistered)?;
+
+			Self::do_delegate_vote(delegator, &account_id, &delegate, scope.clone(), expires_at)?;
+
+			// Emit an event.
+			Self::deposit_event(Event::VoteDelegated { delegator, delegate, scope, expires_at });
+
+			Ok(())
+		}
+
+		/// Revoke your voting delegate
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(2, 2))]
+		pub fn revoke_delegation(origin: OriginFor<T>) -> DispatchResult {
+			let account_id = ensure_signed(origin)?;
+			let delegator = AadhaarPallet::<T>::get_aadhaar_id(&account_id)
+				.ok_or(Error::<T>::VoterNotRegistered)?;
+
+			let delegation = Self::remove_delegation(&delegator).ok_or(Error::<T>::DelegationNotFound)?;
+
+			// Emit an event.
+			Self::deposit_event(Event::DelegationRevoked { delegator, delegate: delegation.delegate });
+
+			Ok(())
+		}
+
+		/// Lock balance until the session ends to weight your vote by it
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(6, 3))]
+		pub fn lock_vote_weight(origin: OriginFor<T>, vote_index: VoteIndex, amount: BalanceOf<T>) -> DispatchResult {
+			let account_id = ensure_signed(origin)?;
+
+			Self::do_lock_vote_weight(&account_id, vote_index, amount)?;
 
 			// Emit an event.
-			Self::deposit_event(Event::Voted { vote_index, candidate });
+			Self::deposit_event(Event::VoteWeightLocked { vote_index, who: account_id, amount });
+
+			Ok(())
+		}
+
+		/// Unlock the balance which weighted your vote once the session ended
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(5, 3))]
+		pub fn unlock_vote_weight(origin: OriginFor<T>, vote_index: VoteIndex) -> DispatchResult {
+			let account_id = ensure_signed(origin)?;
+
+			Self::do_unlock_vote_weight(&account_id, vote_index)?;
+
+			// Emit an event.
+			Self::deposit_event(Event::VoteWeightUnlocked { vote_index, who: account_id });
+
+			Ok(())
+		}
+
+		/// Set the vote weight of voters on a session weighted by the commission
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(2, weights.len() as u64))]
+		pub fn set_vote_weights(
+			origin: OriginFor<T>,
+			vote_index: VoteIndex,
+			weights: Vec<(AadhaarId, VoteCount)>,
+		) -> DispatchResult {
+			// Check if origin is a from a validator
+			T::ElectionCommissionOrigin::ensure_origin(origin)?;
+
+			let config = Sessions::<T>::get(vote_index).ok_or(Error::<T>::VoteSessionNotFound)?;
+			ensure!(config.weight_source == WeightSource::Commission, Error::<T>::WeightSourceMismatch);
+			ensure!(VotingState::<T>::get(vote_index) == VoteState::Idle, Error::<T>::VoteWeightsLocked);
+
+			for (voter_id, weight) in weights.iter() {
+				VoteWeights::<T>::insert(vote_index, voter_id, weight);
+			}
+
+			// Emit an event.
+			Self::deposit_event(Event::VoteWeightsSet { vote_index, count: weights.len() as u32 });
+
+			Ok(())
+		}
+
+		/// Commit to a vote on a secret ballot session with hash(vote_index ++ voter ++ candidate ++ salt)
+		#[pallet::weight(T::WeightInfo::commit_vote(T::MaxRollProofDepth::get()))]
+		pub fn commit_vote(
+			origin: OriginFor<T>,
+			vote_index: VoteIndex,
+			commitment: T::Hash,
+			proof: RollProof<T>,
+		) -> DispatchResult {
+			let account_id = ensure_signed(origin)?;
+
+			// Store the commitment without revealing the candidate
+			Self::do_commit_vote(&account_id, vote_index, commitment, &proof)?;
+
+			// Emit an event.
+			Self::deposit_event(Event::VoteCommitted { vote_index });
+
+			Ok(())
+		}
+
+		/// Cast a ballot on a session which takes more than a single candidate
+		#[pallet::weight(T::WeightInfo::cast_ballot(
+			T::MaxBallotChoices::get().max(T::MaxCandidatesPerSession::get()),
+			T::MaxRollProofDepth::get(),
+		))]
+		pub fn cast_ballot(
+			origin: OriginFor<T>,
+			vote_index: VoteIndex,
+			ballot: BallotChoice<T>,
+			proof: RollProof<T>,
+		) -> DispatchResult {
+			let account_id = ensure_signed(origin)?;
+
+			// Check the ballot against the session and record it
+			Self::do_cast_ballot(&account_id, vote_index, ballot, &proof)?;
+
+			// Emit an event.
+			Self::deposit_event(Event::BallotCast { vote_index });
+
+			Ok(())
+		}
+
+		/// Reveal a committed vote on a secret ballot session and count it
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(3, 3))]
+		pub fn reveal_vote(origin: OriginFor<T>, vote_index: VoteIndex, candidate: AadhaarId, salt: Salt) -> DispatchResult {
+			let account_id = ensure_signed(origin)?;
+
+			// Check the reveal against the commitment and count the vote
+			Self::do_reveal_vote(&account_id, vote_index, candidate, salt)?;
+
+			// Emit an event.
+			Self::deposit_event(Event::VoteRevealed { vote_index, candidate });
 
 			Ok(())
 		}
@@ -189,77 +1076,964 @@ pub mod pallet {
 
 	impl<T: Config> Pallet<T> {
 
-		/// Create voting session and set status to Voting
-		pub fn do_start_voting(account_id: &T::AccountId) -> Result<(AadhaarId, VoteIndex), DispatchError> {
+		/// Create voting session and set status to Voting, or Committing for secret ballots.
+		/// Sessions with a start block stay Idle until on_initialize opens them
+		pub fn do_start_voting(account_id: &T::AccountId, config: SessionConfig<T>) -> Result<(AadhaarId, VoteIndex), DispatchError> {
 
-			let aadhaar_id = AadhaarPallet::<T>::get_aadhaar_id(&account_id);
+			let aadhaar_id = AadhaarPallet::<T>::get_aadhaar_id(account_id);
 			let vote_index = Self::current_vote_index();
-			VotingState::<T>::set(vote_index, VoteState::Voting);
+
+			// Ensure the voting window is in the future and closes after it opens
+			let now = frame_system::Pallet::<T>::block_number();
+			let opens_at = config.starts_at.unwrap_or(now);
+			ensure!(config.starts_at.map_or(true, |starts_at| starts_at > now), Error::<T>::InvalidSchedule);
+			ensure!(config.ends_at.map_or(true, |ends_at| ends_at > opens_at), Error::<T>::InvalidSchedule);
+			// Reveals close on schedule only after commitments did
+			ensure!(
+				config.reveal_ends_at.map_or(true, |reveal_ends_at| {
+					config.secret && config.ends_at.map_or(false, |ends_at| reveal_ends_at > ends_at)
+				}),
+				Error::<T>::InvalidSchedule
+			);
+			// Secret ballots and runoffs are only run on single choice sessions, referendums use
+			// the majority as their pass threshold
+			let single_choice = config.ballot_type == BallotType::FirstPastThePost;
+			ensure!(!config.secret || single_choice, Error::<T>::UnsupportedBallotConfig);
+			ensure!(
+				config.majority.is_none() || single_choice || config.ballot_type == BallotType::Referendum,
+				Error::<T>::UnsupportedBallotConfig
+			);
+			ensure!(
+				config.majority.is_none() || !single_choice || config.runoff_size >= 2,
+				Error::<T>::InvalidRunoffSize
+			);
+			ensure!(config.ballot_type != BallotType::Score { max_score: 0 }, Error::<T>::UnsupportedBallotConfig);
+			// Only plain single choice votes can be taken back off the tally
+			ensure!(!config.allow_recast || (single_choice && !config.secret), Error::<T>::UnsupportedBallotConfig);
+			ensure!(
+				config.weight_source == WeightSource::Equal ||
+					(single_choice && !config.secret) || config.ballot_type == BallotType::Referendum,
+				Error::<T>::UnsupportedBallotConfig
+			);
+			ensure!(
+				config.constituencies.iter().all(|id| AadhaarPallet::<T>::does_constituency_exist(*id)),
+				Error::<T>::ConstituencyNotFound
+			);
+
+			if let Some(starts_at) = config.starts_at {
+				ScheduledStarts::<T>::try_mutate(starts_at, |queue| queue.try_push(vote_index))
+					.map_err(|_| Error::<T>::TooManyScheduled)?;
+			}
+			for ends_at in config.ends_at.iter().chain(config.reveal_ends_at.iter()) {
+				ScheduledEnds::<T>::try_mutate(ends_at, |queue| queue.try_push(vote_index))
+					.map_err(|_| Error::<T>::TooManyScheduled)?;
+			}
+
+			let state = match config.starts_at {
+				Some(_) => VoteState::Idle,
+				None => Self::opening_state(&config),
+			};
+			VotingState::<T>::set(vote_index, state);
+			Sessions::<T>::insert(vote_index, config);
 			ChiefCommissioner::<T>::set(vote_index, aadhaar_id);
 			CurrentVoteIndex::<T>::set(vote_index.saturating_add(1));
 
 			Ok((aadhaar_id.unwrap(), vote_index))
 		}
 
-		/// Set Voting status of a voting session to Ended
-		pub fn do_stop_voting(account_id: &T::AccountId, vote_index: VoteIndex) -> Result<(AadhaarId, VoteIndex), DispatchError> {
+		/// Set Voting status of a voting session to Ended. Secret ballots go from Committing to
+		/// Revealing first and are Ended on the next call
+		pub fn do_stop_voting(account_id: &T::AccountId, vote_index: VoteIndex) -> Result<(AadhaarId, VoteState), DispatchError> {
 
-			ensure!(VotingState::<T>::get(vote_index) == VoteState::Voting, Error::<T>::VotingNotActive);
+			let next_state = Self::do_advance_phase(vote_index)?;
+			let aadhaar_id = AadhaarPallet::<T>::get_aadhaar_id(account_id).unwrap();
 
-			let aadhaar_id = AadhaarPallet::<T>::get_aadhaar_id(&account_id).unwrap();
-			VotingState::<T>::set(vote_index, VoteState::Ended);
+			Ok((aadhaar_id, next_state))
+		}
 
-			Ok((aadhaar_id, vote_index))
+		/// Move a voting session out of its current phase. Voting and Revealing sessions are
+		/// Ended and Committing sessions start Revealing
+		fn do_advance_phase(vote_index: VoteIndex) -> Result<VoteState, DispatchError> {
+
+			let next_state = match VotingState::<T>::get(vote_index) {
+				VoteState::Voting if Self::ballot_type(vote_index) == BallotType::RankedChoice => {
+					// Ranked ballots are counted in on_initialize
+					TallyProgress::<T>::insert(vote_index, RankedTally { round: 1, ..Default::default() });
+					VoteState::Tallying
+				},
+				VoteState::Voting => VoteState::Ended,
+				VoteState::Committing => VoteState::Revealing,
+				VoteState::Revealing => {
+					// Report commitments which were never revealed, they are not counted
+					let unrevealed = SecretTallies::<T>::get(vote_index).unrevealed();
+					if unrevealed > 0 {
+						Self::deposit_event(Event::CommitmentsUnrevealed { vote_index, count: unrevealed });
+					}
+					VoteState::Ended
+				},
+				_ => return Err(Error::<T>::VotingNotActive.into()),
+			};
+
+			VotingState::<T>::set(vote_index, next_state.clone());
+
+			Ok(next_state)
+		}
+
+		/// Ballot type of a voting session
+		pub fn ballot_type(vote_index: VoteIndex) -> BallotType {
+			Sessions::<T>::get(vote_index).map(|config| config.ballot_type).unwrap_or_default()
+		}
+
+		/// State a session enters when voting opens
+		fn opening_state(config: &SessionConfig<T>) -> VoteState {
+			if config.secret { VoteState::Committing } else { VoteState::Voting }
+		}
+
+		/// Emit the event for a session which was started or scheduled
+		fn deposit_start_event(
+			aadhaar_id: AadhaarId,
+			vote_index: VoteIndex,
+			starts_at: Option<T::BlockNumber>,
+			ends_at: Option<T::BlockNumber>,
+		) {
+			match starts_at {
+				Some(starts_at) => Self::deposit_event(Event::VotingScheduled {
+					aadhaar_id,
+					vote_index,
+					starts_at,
+					ends_at,
+				}),
+				None => Self::deposit_event(Event::VotingStarted { aadhaar_id, vote_index }),
+			}
+		}
+
+		/// Emit the event matching the phase a session was moved to
+		fn deposit_phase_event(aadhaar_id: AadhaarId, vote_index: VoteIndex, state: &VoteState) {
+			match state {
+				VoteState::Revealing =>
+					Self::deposit_event(Event::RevealStarted { aadhaar_id, vote_index }),
+				_ => Self::deposit_event(Event::VotingStopped { aadhaar_id, vote_index }),
+			}
+		}
+
+		/// Open a scheduled session unless it was reset in the meantime
+		fn open_scheduled_session(vote_index: VoteIndex) {
+			let (config, aadhaar_id) = match (Sessions::<T>::get(vote_index), ChiefCommissioner::<T>::get(vote_index)) {
+				(Some(config), Some(aadhaar_id)) => (config, aadhaar_id),
+				_ => return,
+			};
+			if VotingState::<T>::get(vote_index) != VoteState::Idle {
+				return
+			}
+
+			VotingState::<T>::set(vote_index, Self::opening_state(&config));
+			Self::deposit_event(Event::VotingStarted { aadhaar_id, vote_index });
+		}
+
+		/// Close a scheduled session, or the reveal phase of a secret ballot, unless it was already
+		/// stopped or reset
+		fn close_scheduled_session(vote_index: VoteIndex, now: T::BlockNumber) {
+			let (config, aadhaar_id) = match (Sessions::<T>::get(vote_index), ChiefCommissioner::<T>::get(vote_index)) {
+				(Some(config), Some(aadhaar_id)) => (config, aadhaar_id),
+				_ => return,
+			};
+
+			// A reveal phase opened early by stop_voting is not closed at ends_at
+			let reveal_ends = config.reveal_ends_at == Some(now);
+			match VotingState::<T>::get(vote_index) {
+				VoteState::Revealing if !reveal_ends => {},
+				VoteState::Voting | VoteState::Committing | VoteState::Revealing => {
+					if let Ok(state) = Self::do_advance_phase(vote_index) {
+						Self::deposit_phase_event(aadhaar_id, vote_index, &state);
+						if state == VoteState::Ended {
+							Self::conclude_session(vote_index);
+						}
+					}
+				},
+				_ => {},
+			}
 		}
 
-		/// Remove all voting session data and set voting status to ended
+		/// Remove the session and its candidates, voter data is purged in on_idle
 		pub fn do_reset_voting(account_id: &T::AccountId, vote_index: VoteIndex) -> Result<(AadhaarId, VoteIndex), DispatchError> {
 
-			let aadhaar_id = AadhaarPallet::<T>::get_aadhaar_id(&account_id).unwrap();
-			VotingState::<T>::set(vote_index, VoteState::Idle);
-			ChiefCommissioner::<T>::remove(vote_index);
+			// Declared results are final
+			ensure!(!Results::<T>::contains_key(vote_index), Error::<T>::ResultAlreadyDeclared);
+			ensure!(VotingState::<T>::get(vote_index) != VoteState::Purging, Error::<T>::SessionPurging);
+			// An index not opened yet would be purged under a session started later
+			ensure!(Sessions::<T>::contains_key(vote_index), Error::<T>::VoteSessionNotFound);
 
-			// No of record to delete 
-			//TODO: To be updated to a config and set limit on max candidates
-			let limit = 20; 
+			let aadhaar_id = AadhaarPallet::<T>::get_aadhaar_id(account_id).unwrap();
+			VotingState::<T>::set(vote_index, VoteState::Purging);
+			ChiefCommissioner::<T>::remove(vote_index);
+			Sessions::<T>::remove(vote_index);
+			SecretTallies::<T>::remove(vote_index);
+			Proposals::<T>::remove(vote_index);
+			ReferendumTallies::<T>::remove(vote_index);
+			NotaTallies::<T>::remove(vote_index);
+			Turnout::<T>::remove(vote_index);
+			VoterTrees::<T>::remove(vote_index);
+			for (candidate, nomination) in Nominations::<T>::drain_prefix(vote_index) {
+				Self::return_deposit(&candidate, &nomination);
+			}
+
+			// Candidate data is bounded by the session limit
+			let limit = T::MaxCandidatesPerSession::get();
 			let _ = Candidates::<T>::clear_prefix(vote_index, limit, None);
-			let _ = Votes::<T>::clear_prefix(vote_index, limit, None);
+			let _ = CandidateProfiles::<T>::clear_prefix(vote_index, limit, None);
+			let _ = RoundCounts::<T>::clear_prefix(vote_index, limit, None);
+			let _ = Eliminated::<T>::clear_prefix(vote_index, limit, None);
+			let _ = EliminationLog::<T>::clear_prefix(vote_index, limit, None);
+			CandidateCount::<T>::remove(vote_index);
+			RankedBallotCount::<T>::remove(vote_index);
+			TallyProgress::<T>::remove(vote_index);
+
+			// Voter data can outgrow a block, it is removed in on_idle
+			PurgeCursors::<T>::insert(vote_index, PurgeProgress::default());
 
 			Ok((aadhaar_id, vote_index))
 		}
 
+		/// Candidates of a voting session ordered by votes, ties ordered by aadhaar id
+		pub fn standings(vote_index: VoteIndex) -> Vec<Candidate> {
+			let mut standings: Vec<Candidate> = Candidates::<T>::iter_prefix_values(vote_index).collect();
+			standings.sort_by(|a, b| b.vote_count.cmp(&a.vote_count).then(a.aadhaar_id.cmp(&b.aadhaar_id)));
+			standings
+		}
+
+		/// Decide an ended session, or start a runoff when the leader missed the majority
+		pub(crate) fn conclude_session(vote_index: VoteIndex) {
+			let config = match Sessions::<T>::get(vote_index) {
+				Some(config) => config,
+				None => return,
+			};
+
+			if config.ballot_type == BallotType::Referendum {
+				return Self::conclude_referendum(vote_index, config.majority.unwrap_or(Perbill::from_percent(50)))
+			}
+
+			let standings = Self::standings(vote_index);
+			let total = standings.iter().fold(0, |total: VoteCount, c| total.saturating_add(c.vote_count));
+			// Ranked tallies are best round counts and approval or score ballots count for several
+			// candidates, these add up to more than the ballots cast
+			let cast = match config.ballot_type {
+				BallotType::RankedChoice => RankedBallotCount::<T>::get(vote_index).into(),
+				BallotType::Approval | BallotType::Score { .. } => Turnout::<T>::get(vote_index).into(),
+				_ => total,
+			};
+			Self::settle_deposits(vote_index, cast);
+			let leader = standings.first();
+			let runner_up = standings.get(1);
+
+			// None of the Above counts towards the votes cast and may reject every candidate
+			let nota = NotaTallies::<T>::get(vote_index);
+			let total = total.saturating_add(nota);
+			if nota > 0 && nota > leader.map_or(0, |c| c.vote_count) {
+				Self::deposit_event(Event::NotaWon { vote_index, votes: nota });
+				match config.nota_rule {
+					NotaRule::Disregard => (),
+					NotaRule::NoWinner => {
+						Self::declare_result(vote_index, standings, nota, Vec::new());
+						Self::deposit_event(Event::ElectionDecided {
+							vote_index,
+							first_round: Self::first_round(vote_index),
+							winner: None,
+						});
+						return
+					},
+					NotaRule::Repoll => {
+						Self::declare_result(vote_index, standings, nota, Vec::new());
+						// The commission fills the repoll before it opens
+						let opens_at = frame_system::Pallet::<T>::block_number().saturating_add(T::RepollDelay::get());
+						let repoll_index = Self::start_runoff(vote_index, config, &[], opens_at);
+						Self::deposit_event(Event::RepollStarted { vote_index, repoll_index });
+						return
+					},
+				}
+			}
+
+			if let Some(majority) = config.majority {
+				let reached = leader.map_or(false, |c| c.vote_count > majority.mul_floor(total));
+				// A runoff with every candidate would just repeat this session
+				let narrows = standings.len() > config.runoff_size as usize;
+
+				if !reached && narrows {
+					let finalists = standings
+						.iter()
+						.take(config.runoff_size as usize)
+						.map(|c| c.aadhaar_id)
+						.collect::<Vec<_>>();
+					Self::declare_result(vote_index, standings, nota, Vec::new());
+					let runoff_index = Self::start_runoff(vote_index, config, &finalists, frame_system::Pallet::<T>::block_number());
+					Self::deposit_event(Event::RunoffStarted { vote_index, runoff_index, candidates: finalists });
+					return
+				}
+			}
+
+			// Leader wins unless tied with the runner up
+			let winner = match (leader, runner_up) {
+				(Some(leader), Some(runner_up)) if leader.vote_count == runner_up.vote_count => None,
+				(leader, _) => leader.map(|c| c.aadhaar_id),
+			};
+			let top = leader.map_or(0, |c| c.vote_count);
+			let winners = standings
+				.iter()
+				.take_while(|c| c.vote_count == top)
+				.map(|c| c.aadhaar_id)
+				.collect::<Vec<_>>();
+			Self::declare_result(vote_index, standings, nota, winners.clone());
+			if winner.is_none() && winners.len() > 1 && Self::start_tie_break(vote_index, config, winners) {
+				return
+			}
+			Self::deposit_event(Event::ElectionDecided {
+				vote_index,
+				first_round: Self::first_round(vote_index),
+				winner,
+			});
+		}
+
+		/// Write the outcome of a session to Results unless it was already declared
+		fn declare_result(vote_index: VoteIndex, standings: Vec<Candidate>, nota: VoteCount, winners: Vec<AadhaarId>) {
+			if Results::<T>::contains_key(vote_index) {
+				return
+			}
+
+			let tied = winners.len() > 1;
+			Results::<T>::insert(vote_index, ElectionResult {
+				standings,
+				nota,
+				winners: winners.clone(),
+				turnout: Turnout::<T>::get(vote_index),
+				tied,
+				referendum: None,
+			});
+			Self::schedule_archive(vote_index);
+			Self::deposit_event(Event::ResultDeclared { vote_index, winners, tied });
+		}
+
+		/// Return the deposits of candidates who reached the vote-share threshold and slash the rest
+		fn settle_deposits(vote_index: VoteIndex, total: VoteCount) {
+			let threshold = T::DepositReturnThreshold::get().mul_ceil(total);
+
+			for (candidate, nomination) in Nominations::<T>::drain_prefix(vote_index) {
+				let votes = Candidates::<T>::get(vote_index, candidate).vote_count;
+				if !nomination.accepted || votes >= threshold {
+					Self::return_deposit(&candidate, &nomination);
+					Self::deposit_event(Event::DepositReturned { vote_index, candidate, amount: nomination.deposit });
+				} else {
+					let (imbalance, _) = T::Currency::slash_reserved(&nomination.account_id, nomination.deposit);
+					T::DepositSlash::on_unbalanced(imbalance);
+					Self::deposit_event(Event::DepositSlashed { vote_index, candidate, amount: nomination.deposit });
+				}
+			}
+		}
+
+		/// Return a nomination deposit to the account the candidate's aadhaar is linked to, which
+		/// is no longer the nominating account after a rotation
+		fn return_deposit(candidate_id: &AadhaarId, nomination: &Nomination<T>) {
+			match AadhaarPallet::<T>::get_account_id(candidate_id) {
+				Some(account_id) if account_id != nomination.account_id => {
+					let _ = T::Currency::repatriate_reserved(
+						&nomination.account_id,
+						&account_id,
+						nomination.deposit,
+						BalanceStatus::Free,
+					);
+				},
+				_ => {
+					T::Currency::unreserve(&nomination.account_id, nomination.deposit);
+				},
+			}
+		}
+
+		/// Approve the proposal when Yes exceeds the majority of Yes and No votes
+		fn conclude_referendum(vote_index: VoteIndex, majority: Perbill) {
+			let tally = ReferendumTallies::<T>::get(vote_index);
+			let approved = tally.yes > majority.mul_floor(tally.yes.saturating_add(tally.no));
+			if Results::<T>::contains_key(vote_index) {
+				return
+			}
+
+			// The outcome is final, it blocks a reset and outlives the archive
+			Results::<T>::insert(vote_index, ElectionResult {
+				turnout: Turnout::<T>::get(vote_index),
+				referendum: Some(ReferendumOutcome { approved, tally: tally.clone() }),
+				..Default::default()
+			});
+			Self::schedule_archive(vote_index);
+
+			Self::deposit_event(Event::ReferendumDecided { vote_index, approved, tally });
+		}
+
+		/// Start a runoff session between the finalists of the given session. It opens at
+		/// opens_at, sessions opening later stay Idle for nominations until then
+		pub(crate) fn start_runoff(
+			parent: VoteIndex,
+			mut config: SessionConfig<T>,
+			finalists: &[AadhaarId],
+			opens_at: T::BlockNumber,
+		) -> VoteIndex {
+			let vote_index = Self::current_vote_index();
+			CurrentVoteIndex::<T>::set(vote_index.saturating_add(1));
+
+			let now = frame_system::Pallet::<T>::block_number();
+			let mut starts_at = opens_at.max(now);
+			if starts_at > now {
+				// Without room in the schedule the session opens at the next free block
+				while ScheduledStarts::<T>::try_mutate(starts_at, |queue| queue.try_push(vote_index)).is_err() {
+					starts_at = starts_at.saturating_add(One::one());
+				}
+			}
+
+			// If the parent had a fixed window, the runoff runs for as long
+			let parent_ends_at = config.ends_at;
+			config.ends_at = match (config.starts_at, config.ends_at) {
+				(Some(parent_starts_at), Some(ends_at)) => Some(starts_at.saturating_add(ends_at.saturating_sub(parent_starts_at))),
+				_ => None,
+			};
+			// Reveals are kept open for as long as in the parent
+			config.reveal_ends_at = match (config.ends_at, config.reveal_ends_at) {
+				(Some(runoff_ends_at), Some(reveal_ends_at)) => parent_ends_at
+					.map(|ends_at| runoff_ends_at.saturating_add(reveal_ends_at.saturating_sub(ends_at))),
+				_ => None,
+			};
+			config.starts_at = if starts_at > now { Some(starts_at) } else { None };
+			if let Some(ends_at) = config.ends_at {
+				// Without room in the schedule the runoff is closed with stop_voting
+				if ScheduledEnds::<T>::try_mutate(ends_at, |queue| queue.try_push(vote_index)).is_err() {
+					config.ends_at = None;
+					config.reveal_ends_at = None;
+				}
+			}
+			if let Some(reveal_ends_at) = config.reveal_ends_at {
+				if ScheduledEnds::<T>::try_mutate(reveal_ends_at, |queue| queue.try_push(vote_index)).is_err() {
+					config.reveal_ends_at = None;
+				}
+			}
+
+			if let Some(aadhaar_id) = ChiefCommissioner::<T>::get(parent) {
+				ChiefCommissioner::<T>::insert(vote_index, aadhaar_id);
+			}
+			for candidate_id in finalists {
+				Candidates::<T>::insert(vote_index, candidate_id, Candidate {
+					aadhaar_id: *candidate_id,
+					vote_count: Default::default(),
+				});
+				if let Some(profile) = CandidateProfiles::<T>::get(parent, candidate_id) {
+					CandidateProfiles::<T>::insert(vote_index, candidate_id, profile);
+				}
+			}
+			CandidateCount::<T>::insert(vote_index, finalists.len() as u32);
+			let state = match config.starts_at {
+				Some(_) => VoteState::Idle,
+				None => Self::opening_state(&config),
+			};
+			VotingState::<T>::insert(vote_index, state);
+			Sessions::<T>::insert(vote_index, config);
+			RunoffOf::<T>::insert(parent, vote_index);
+			ParentSession::<T>::insert(vote_index, parent);
+
+			vote_index
+		}
+
+		/// First session of the chain of runoffs the given session belongs to
+		pub fn first_round(vote_index: VoteIndex) -> VoteIndex {
+			let mut round = vote_index;
+			while let Some(parent) = ParentSession::<T>::get(round) {
+				round = parent;
+			}
+			round
+		}
+
 		/// Add candidates for voting session
-		pub fn do_add_candidates(vote_index: VoteIndex, candidates: &Vec<T::AccountId>) -> DispatchResult {
+		pub fn do_add_candidates(vote_index: VoteIndex, candidates: &[T::AccountId]) -> DispatchResult {
+			// Referendum options are fixed
+			ensure!(Self::ballot_type(vote_index) != BallotType::Referendum, Error::<T>::BallotTypeMismatch);
+			// Candidates added later could not set their profile
+			ensure!(Self::candidates_open(vote_index), Error::<T>::CandidatesLocked);
+
+			// Every candidate needs a registered aadhaar
+			let mut candidate_ids = candidates
+				.iter()
+				.map(|candidate| AadhaarPallet::<T>::get_aadhaar_id(candidate))
+				.collect::<Option<Vec<_>>>()
+				.ok_or(Error::<T>::CandidateNotRegistered)?;
+			ensure!(!candidate_ids.contains(&NOTA), Error::<T>::ReservedCandidateId);
+
+			// Only new candidates take up a slot of the session
+			candidate_ids.sort();
+			candidate_ids.dedup();
+			let added = candidate_ids
+				.iter()
+				.filter(|candidate_id| {
+					!Candidates::<T>::contains_key(vote_index, candidate_id) &&
+						!Nominations::<T>::contains_key(vote_index, candidate_id)
+				})
+				.count() as u32;
+			Self::reserve_candidate_slots(vote_index, added)?;
+
 			// Add candidates to the storage
-			candidates.into_iter().for_each(|candidate| {
-				let candidate_id = AadhaarPallet::<T>::get_aadhaar_id(candidate);
-				if let Some(candidate_id) = candidate_id {
-					Candidates::<T>::set(vote_index, candidate_id, Candidate {
-						aadhaar_id: candidate_id,
-						vote_count: Default::default(),
-					});
-				}
+			candidate_ids.into_iter().for_each(|candidate_id| {
+				Candidates::<T>::set(vote_index, candidate_id, Candidate {
+					aadhaar_id: candidate_id,
+					vote_count: Default::default(),
+				});
+			});
+
+			Ok(())
+		}
+
+		/// Take up candidate slots of a session, failing once the session is full
+		fn reserve_candidate_slots(vote_index: VoteIndex, slots: u32) -> DispatchResult {
+			CandidateCount::<T>::try_mutate(vote_index, |count| -> DispatchResult {
+				let total = count.saturating_add(slots);
+				ensure!(total <= T::MaxCandidatesPerSession::get(), Error::<T>::TooManyCandidates);
+				*count = total;
+				Ok(())
+			})
+		}
+
+		/// Reserve the nomination deposit of a registered aadhaar holder
+		pub fn do_nominate(account_id: &T::AccountId, vote_index: VoteIndex) -> Result<(AadhaarId, BalanceOf<T>), DispatchError> {
+			let candidate_id = AadhaarPallet::<T>::get_aadhaar_id(account_id)
+				.ok_or(Error::<T>::VoterNotRegistered)?;
+			ensure!(candidate_id != NOTA, Error::<T>::ReservedCandidateId);
+
+			// Nominations are taken on scheduled sessions which did not open yet
+			ensure!(Sessions::<T>::contains_key(vote_index), Error::<T>::VoteSessionNotFound);
+			ensure!(VotingState::<T>::get(vote_index) == VoteState::Idle, Error::<T>::NominationsClosed);
+			ensure!(Self::ballot_type(vote_index) != BallotType::Referendum, Error::<T>::BallotTypeMismatch);
+			ensure!(
+				!Nominations::<T>::contains_key(vote_index, candidate_id) &&
+					!Candidates::<T>::contains_key(vote_index, candidate_id),
+				Error::<T>::AlreadyNominated
+			);
+
+			let deposit = T::NominationDeposit::get();
+			T::Currency::reserve(account_id, deposit)?;
+			Self::reserve_candidate_slots(vote_index, 1)?;
+			Nominations::<T>::insert(vote_index, candidate_id, Nomination {
+				account_id: account_id.clone(),
+				deposit,
+				accepted: false,
+			});
+
+			Ok((candidate_id, deposit))
+		}
+
+		/// Add the nominee to the candidates of the session
+		pub fn do_accept_nomination(vote_index: VoteIndex, candidate_id: AadhaarId) -> DispatchResult {
+			ensure!(VotingState::<T>::get(vote_index) == VoteState::Idle, Error::<T>::NominationsClosed);
+
+			Nominations::<T>::try_mutate(vote_index, candidate_id, |nomination| -> DispatchResult {
+				let nomination = nomination.as_mut().ok_or(Error::<T>::NominationNotFound)?;
+				ensure!(!nomination.accepted, Error::<T>::NominationAlreadyAccepted);
+				nomination.accepted = true;
+				Ok(())
+			})?;
+			Candidates::<T>::insert(vote_index, candidate_id, Candidate {
+				aadhaar_id: candidate_id,
+				vote_count: Default::default(),
 			});
 
 			Ok(())
 		}
 
-		/// Cast vote on candidate
-		pub fn do_vote(voter_acc: &T::AccountId, vote_index: VoteIndex, candidate_id: AadhaarId) -> DispatchResult {
-			let voter_id = AadhaarPallet::<T>::get_aadhaar_id(&voter_acc).unwrap();
+		/// Store the profile of a candidate until polling opens
+		pub fn do_set_candidate_profile(vote_index: VoteIndex, candidate_id: AadhaarId, profile: CandidateProfile<T>) -> DispatchResult {
+			ensure!(Candidates::<T>::contains_key(vote_index, candidate_id), Error::<T>::CandidateNotFound);
+			ensure!(Self::candidates_open(vote_index), Error::<T>::ProfileLocked);
+			if let Some(party_id) = profile.party {
+				ensure!(Parties::<T>::contains_key(party_id), Error::<T>::PartyNotFound);
+			}
+
+			CandidateProfiles::<T>::insert(vote_index, candidate_id, profile);
+
+			Ok(())
+		}
 
+		/// Whether candidates and their profiles can still change. Scheduled sessions lock them when
+		/// they open, sessions opened at once keep them open until the first ballot is cast
+		pub(crate) fn candidates_open(vote_index: VoteIndex) -> bool {
+			match VotingState::<T>::get(vote_index) {
+				VoteState::Idle => true,
+				VoteState::Voting | VoteState::Committing => {
+					Sessions::<T>::get(vote_index).map_or(false, |config| config.starts_at.is_none()) &&
+						Turnout::<T>::get(vote_index) == 0
+				},
+				_ => false,
+			}
+		}
+
+		/// Drop a nomination before polling opens and return its deposit
+		pub fn do_remove_nomination(vote_index: VoteIndex, candidate_id: AadhaarId) -> DispatchResult {
+			ensure!(VotingState::<T>::get(vote_index) == VoteState::Idle, Error::<T>::NominationsClosed);
+
+			let nomination = Nominations::<T>::take(vote_index, candidate_id).ok_or(Error::<T>::NominationNotFound)?;
+			Self::return_deposit(&candidate_id, &nomination);
+			CandidateCount::<T>::mutate(vote_index, |count| *count = count.saturating_sub(1));
+			Candidates::<T>::remove(vote_index, candidate_id);
+			CandidateProfiles::<T>::remove(vote_index, candidate_id);
+
+			Ok(())
+		}
+
+		/// Cast vote on candidate, returns the candidate of the replaced vote when recasting
+		pub fn do_vote(
+			voter_id: AadhaarId,
+			vote_index: VoteIndex,
+			candidate_id: AadhaarId,
+			proof: &[T::Hash],
+		) -> Result<Option<AadhaarId>, DispatchError> {
 			// Ensure voting session exists and is active and user didn't vote already
-			ensure!(Candidates::<T>::contains_key(vote_index, candidate_id), Error::<T>::VoteSessionNotFound);
+			ensure!(Self::is_ballot_option(vote_index, &candidate_id), Error::<T>::VoteSessionNotFound);
 			ensure!(VotingState::<T>::get(vote_index) == VoteState::Voting, Error::<T>::VotingNotActive);
-			ensure!(Votes::<T>::contains_key(vote_index, voter_id) == false, Error::<T>::VoteAlreadyCast);
+			ensure!(Self::ballot_type(vote_index) == BallotType::FirstPastThePost, Error::<T>::BallotTypeMismatch);
+			Self::ensure_eligible(vote_index, &voter_id, proof)?;
+
+			let config = Sessions::<T>::get(vote_index).unwrap_or_default();
+			let weight = Self::vote_weight(vote_index, &voter_id, &config.weight_source)?;
+			if !config.allow_recast {
+				ensure!(Votes::<T>::contains_key(vote_index, voter_id) == false, Error::<T>::VoteAlreadyCast);
+				Self::add_votes(vote_index, &candidate_id, weight);
+				Self::record_ballot(vote_index, voter_id);
+				return Ok(None)
+			}
+
+			// Take the replaced vote off the tally before counting the new one, the weight cannot
+			// change once the voter voted
+			let replaced = VotedFor::<T>::get(vote_index, voter_id);
+			match replaced {
+				Some(previous_id) => Self::remove_votes(vote_index, &previous_id, weight),
+				None => Self::record_ballot(vote_index, voter_id),
+			}
+			Self::add_votes(vote_index, &candidate_id, weight);
+			VotedFor::<T>::insert(vote_index, voter_id, candidate_id);
+
+			Ok(replaced)
+		}
+
+		/// Weight of the voter's vote on the session
+		fn vote_weight(vote_index: VoteIndex, voter_id: &AadhaarId, source: &WeightSource) -> Result<VoteCount, DispatchError> {
+			let weight = match source {
+				WeightSource::Equal => return Ok(1),
+				WeightSource::LockedBalance => LockFunders::<T>::get(voter_id, vote_index)
+					.and_then(|account_id| VoteLocks::<T>::get(account_id, vote_index))
+					.map_or(0, |amount| amount.saturated_into::<VoteCount>()),
+				WeightSource::Commission => VoteWeights::<T>::get(vote_index, voter_id),
+			};
+			ensure!(!weight.is_zero(), Error::<T>::NoVoteWeight);
+
+			Ok(weight)
+		}
 
-			let mut candidate: Candidate = Candidates::<T>::get(vote_index, candidate_id);
-			candidate.vote_count = candidate.vote_count + 1;
-			Candidates::<T>::set(vote_index, candidate_id, candidate);
+		/// Lock balance of the voter until the session ends
+		pub fn do_lock_vote_weight(account_id: &T::AccountId, vote_index: VoteIndex, amount: BalanceOf<T>) -> DispatchResult {
+			let voter_id = AadhaarPallet::<T>::get_aadhaar_id(account_id)
+				.ok_or(Error::<T>::VoterNotRegistered)?;
+			let config = Sessions::<T>::get(vote_index).ok_or(Error::<T>::VoteSessionNotFound)?;
+			ensure!(config.weight_source == WeightSource::LockedBalance, Error::<T>::WeightSourceMismatch);
+			ensure!(
+				matches!(VotingState::<T>::get(vote_index), VoteState::Idle | VoteState::Voting),
+				Error::<T>::VotingNotActive
+			);
+			ensure!(!Votes::<T>::contains_key(vote_index, voter_id), Error::<T>::VoteAlreadyCast);
+			// A rotated aadhaar keeps the lock of its old account and the same funds cannot back
+			// another aadhaar's vote on the session
+			ensure!(
+				!LockFunders::<T>::contains_key(voter_id, vote_index) &&
+					!VoteLocks::<T>::contains_key(account_id, vote_index),
+				Error::<T>::VoteWeightAlreadyLocked
+			);
+			ensure!(
+				!amount.is_zero() && amount <= T::Currency::free_balance(account_id),
+				Error::<T>::InvalidLockAmount
+			);
+
+			// The same balance can back votes on several sessions, the lock covers the largest
+			VoteLocks::<T>::insert(account_id, vote_index, amount);
+			LockFunders::<T>::insert(voter_id, vote_index, account_id);
+			T::Currency::extend_lock(BALLOT_LOCK_ID, account_id, amount, WithdrawReasons::all());
+
+			Ok(())
+		}
+
+		/// Release the balance locked for an ended session from the account that funded it
+		pub fn do_unlock_vote_weight(account_id: &T::AccountId, vote_index: VoteIndex) -> DispatchResult {
+			let voter_id = AadhaarPallet::<T>::get_aadhaar_id(account_id)
+				.ok_or(Error::<T>::VoterNotRegistered)?;
+			let funder = LockFunders::<T>::get(voter_id, vote_index).ok_or(Error::<T>::NoVoteWeight)?;
+			// Reset sessions are gone, others hold the lock until they end or are decided
+			ensure!(
+				!Sessions::<T>::contains_key(vote_index) ||
+					Results::<T>::contains_key(vote_index) ||
+					matches!(VotingState::<T>::get(vote_index), VoteState::Ended | VoteState::Archived),
+				Error::<T>::SessionNotEnded
+			);
+
+			LockFunders::<T>::remove(voter_id, vote_index);
+			VoteLocks::<T>::remove(&funder, vote_index);
+			match VoteLocks::<T>::iter_prefix_values(&funder).max() {
+				Some(amount) => T::Currency::set_lock(BALLOT_LOCK_ID, &funder, amount, WithdrawReasons::all()),
+				None => T::Currency::remove_lock(BALLOT_LOCK_ID, &funder),
+			}
+
+			Ok(())
+		}
+
+		/// Register the delegate of a voter, replacing any earlier delegate
+		pub fn do_delegate_vote(
+			delegator: AadhaarId,
+			delegator_acc: &T::AccountId,
+			delegate: &T::AccountId,
+			scope: DelegationScope,
+			expires_at: T::BlockNumber,
+		) -> DispatchResult {
+			let now = frame_system::Pallet::<T>::block_number();
+			ensure!(expires_at > now && delegate != delegator_acc, Error::<T>::InvalidDelegation);
+
+			Self::remove_delegation(&delegator);
+			DelegatorsOf::<T>::try_mutate(delegate, |delegators| -> DispatchResult {
+				// Expired delegations no longer take up a slot
+				delegators.retain(|id| {
+					let active = Delegations::<T>::get(id).map_or(false, |d| d.expires_at > now);
+					if !active {
+						Delegations::<T>::remove(id);
+					}
+					active
+				});
+				delegators.try_push(delegator).map_err(|_| Error::<T>::TooManyDelegators)?;
+				Ok(())
+			})?;
+			Delegations::<T>::insert(delegator, Delegation { delegate: delegate.clone(), scope, expires_at });
+
+			Ok(())
+		}
+
+		/// Remove the delegation of a voter from both sides
+		fn remove_delegation(delegator: &AadhaarId) -> Option<Delegation<T>> {
+			let delegation = Delegations::<T>::take(delegator)?;
+			DelegatorsOf::<T>::mutate_exists(&delegation.delegate, |delegators| {
+				if let Some(list) = delegators {
+					list.retain(|id| id != delegator);
+					if list.is_empty() {
+						*delegators = None;
+					}
+				}
+			});
+			Some(delegation)
+		}
+
+		/// Ensure the account is the voter's delegate for the session and the delegation is active
+		pub(crate) fn ensure_delegate(account_id: &T::AccountId, delegator: &AadhaarId, vote_index: VoteIndex) -> DispatchResult {
+			let delegation = Delegations::<T>::get(delegator).ok_or(Error::<T>::DelegationNotFound)?;
+			// A revoked aadhaar is locked out at once, its delegate cannot vote for it either
+			ensure!(AadhaarPallet::<T>::get_account_id(delegator).is_some(), Error::<T>::VoterNotRegistered);
+			let now = frame_system::Pallet::<T>::block_number();
+			ensure!(
+				&delegation.delegate == account_id && delegation.scope.covers(vote_index) && now < delegation.expires_at,
+				Error::<T>::NotDelegate
+			);
+
+			Ok(())
+		}
+
+		/// Ensure the voter is assigned to a constituency the session is bound to and, when the
+		/// session has an electoral roll, that the proof shows the voter is on it
+		pub(crate) fn ensure_eligible(vote_index: VoteIndex, voter_id: &AadhaarId, proof: &[T::Hash]) -> DispatchResult {
+			let config = Sessions::<T>::get(vote_index).unwrap_or_default();
+
+			if !config.constituencies.is_empty() {
+				let constituency_id = AadhaarPallet::<T>::constituency_of(voter_id);
+				ensure!(
+					constituency_id.map_or(false, |id| config.constituencies.contains(&id)),
+					Error::<T>::VoterNotInElectorate
+				);
+			}
+			if let Some(root) = config.electoral_roll {
+				ensure!(Self::verify_roll_proof(&root, voter_id, proof), Error::<T>::NotOnElectoralRoll);
+			}
+
+			Ok(())
+		}
+
+		/// Record a ballot which lists several candidates
+		pub fn do_cast_ballot(
+			voter_acc: &T::AccountId,
+			vote_index: VoteIndex,
+			ballot: BallotChoice<T>,
+			proof: &[T::Hash],
+		) -> DispatchResult {
+			let voter_id = AadhaarPallet::<T>::get_aadhaar_id(voter_acc)
+				.ok_or(Error::<T>::VoterNotRegistered)?;
+
+			// Ensure voting session is active and user didn't vote already
+			ensure!(VotingState::<T>::get(vote_index) == VoteState::Voting, Error::<T>::VotingNotActive);
+			Self::ensure_eligible(vote_index, &voter_id, proof)?;
+			ensure!(!Votes::<T>::contains_key(vote_index, voter_id), Error::<T>::VoteAlreadyCast);
+
+			match ballot {
+				BallotChoice::Ranked(ranking) => {
+					ensure!(Self::ballot_type(vote_index) == BallotType::RankedChoice, Error::<T>::BallotTypeMismatch);
+					if Self::is_nota_ballot(&ranking) {
+						// NOTA ballots reject every candidate and take no part in the runoff rounds
+						Self::add_votes(vote_index, &NOTA, 1);
+					} else {
+						Self::ensure_valid_choices(vote_index, &ranking)?;
+
+						let ballot_index = RankedBallotCount::<T>::get(vote_index);
+						RankedBallots::<T>::insert(vote_index, ballot_index, ranking);
+						RankedBallotCount::<T>::insert(vote_index, ballot_index.saturating_add(1));
+					}
+				},
+				BallotChoice::Approval(approved) => {
+					ensure!(Self::ballot_type(vote_index) == BallotType::Approval, Error::<T>::BallotTypeMismatch);
+					if !Self::is_nota_ballot(&approved) {
+						Self::ensure_valid_choices(vote_index, &approved)?;
+					}
+
+					for candidate_id in approved.iter() {
+						Self::add_votes(vote_index, candidate_id, 1);
+					}
+				},
+				BallotChoice::Score(scores) => {
+					let max_score = match Self::ballot_type(vote_index) {
+						BallotType::Score { max_score } => max_score,
+						_ => return Err(Error::<T>::BallotTypeMismatch.into()),
+					};
+					let scored = scores.iter().map(|(candidate_id, _)| *candidate_id).collect::<Vec<_>>();
+					if Self::is_nota_ballot(&scored) {
+						// The score of a NOTA ballot carries no meaning, it counts once
+						Self::add_votes(vote_index, &NOTA, 1);
+						Self::record_ballot(vote_index, voter_id);
+						return Ok(())
+					}
+					Self::ensure_valid_choices(vote_index, &scored)?;
+					ensure!(scores.iter().all(|(_, score)| *score <= max_score), Error::<T>::ScoreOutOfRange);
+
+					for (candidate_id, score) in scores.iter() {
+						Self::add_votes(vote_index, candidate_id, (*score).into());
+					}
+				},
+				BallotChoice::Referendum(choice) => {
+					let config = Sessions::<T>::get(vote_index).unwrap_or_default();
+					ensure!(config.ballot_type == BallotType::Referendum, Error::<T>::BallotTypeMismatch);
+					let weight = Self::vote_weight(vote_index, &voter_id, &config.weight_source)?;
+
+					ReferendumTallies::<T>::mutate(vote_index, |tally| match choice {
+						ReferendumChoice::Yes => tally.yes = tally.yes.saturating_add(weight),
+						ReferendumChoice::No => tally.no = tally.no.saturating_add(weight),
+						ReferendumChoice::Abstain => tally.abstain = tally.abstain.saturating_add(weight),
+					});
+				},
+			}
+			Self::record_ballot(vote_index, voter_id);
+
+			Ok(())
+		}
+
+		/// Ballot listing only None of the Above, which rejects every candidate
+		fn is_nota_ballot(choices: &[AadhaarId]) -> bool {
+			choices == &[NOTA]
+		}
+
+		/// Check a ballot only lists candidates of the session, each at most once. NOTA can
+		/// only be listed alone
+		pub(crate) fn ensure_valid_choices(vote_index: VoteIndex, choices: &[AadhaarId]) -> DispatchResult {
+			ensure!(!choices.is_empty(), Error::<T>::EmptyBallot);
+			ensure!(!choices.contains(&NOTA), Error::<T>::ReservedCandidateId);
+			for (position, candidate_id) in choices.iter().enumerate() {
+				ensure!(!choices[..position].contains(candidate_id), Error::<T>::DuplicateChoice);
+				ensure!(Candidates::<T>::contains_key(vote_index, candidate_id), Error::<T>::CandidateNotFound);
+			}
+
+			Ok(())
+		}
+
+		/// Mark the voter as having voted and count the ballot towards the turnout
+		fn record_ballot(vote_index: VoteIndex, voter_id: AadhaarId) {
 			Votes::<T>::set(vote_index, voter_id, true);
+			Turnout::<T>::mutate(vote_index, |turnout| *turnout = turnout.saturating_add(1));
+			Self::append_voter(vote_index, &voter_id);
+		}
+
+		/// Check the candidate, or None of the Above, can be voted for on the session
+		fn is_ballot_option(vote_index: VoteIndex, candidate_id: &AadhaarId) -> bool {
+			match *candidate_id {
+				NOTA => Sessions::<T>::contains_key(vote_index),
+				_ => Candidates::<T>::contains_key(vote_index, candidate_id),
+			}
+		}
+
+		/// Add votes to the tally of a candidate, or of None of the Above
+		fn add_votes(vote_index: VoteIndex, candidate_id: &AadhaarId, votes: VoteCount) {
+			if *candidate_id == NOTA {
+				return NotaTallies::<T>::mutate(vote_index, |nota| *nota = nota.saturating_add(votes))
+			}
+
+			Candidates::<T>::mutate(vote_index, candidate_id, |candidate| {
+				candidate.vote_count = candidate.vote_count.saturating_add(votes);
+			});
+		}
+
+		/// Take votes off the tally of a candidate, or of None of the Above
+		fn remove_votes(vote_index: VoteIndex, candidate_id: &AadhaarId, votes: VoteCount) {
+			if *candidate_id == NOTA {
+				return NotaTallies::<T>::mutate(vote_index, |nota| *nota = nota.saturating_sub(votes))
+			}
+
+			Candidates::<T>::mutate(vote_index, candidate_id, |candidate| {
+				candidate.vote_count = candidate.vote_count.saturating_sub(votes);
+			});
+		}
+
+		/// Store a vote commitment on a secret ballot session
+		pub fn do_commit_vote(
+			voter_acc: &T::AccountId,
+			vote_index: VoteIndex,
+			commitment: T::Hash,
+			proof: &[T::Hash],
+		) -> DispatchResult {
+			let voter_id = AadhaarPallet::<T>::get_aadhaar_id(voter_acc)
+				.ok_or(Error::<T>::VoterNotRegistered)?;
+
+			// Ensure session is committing and user didn't vote already
+			ensure!(VotingState::<T>::get(vote_index) == VoteState::Committing, Error::<T>::CommitPhaseNotActive);
+			Self::ensure_eligible(vote_index, &voter_id, proof)?;
+			ensure!(!Votes::<T>::contains_key(vote_index, voter_id), Error::<T>::VoteAlreadyCast);
+
+			Commitments::<T>::insert(vote_index, voter_id, commitment);
+			Self::record_ballot(vote_index, voter_id);
+			SecretTallies::<T>::mutate(vote_index, |tally| tally.committed = tally.committed.saturating_add(1));
 
 			Ok(())
 		}
 
+		/// Check a revealed vote against its commitment and count it
+		pub fn do_reveal_vote(voter_acc: &T::AccountId, vote_index: VoteIndex, candidate_id: AadhaarId, salt: Salt) -> DispatchResult {
+			let voter_id = AadhaarPallet::<T>::get_aadhaar_id(voter_acc)
+				.ok_or(Error::<T>::VoterNotRegistered)?;
+
+			ensure!(VotingState::<T>::get(vote_index) == VoteState::Revealing, Error::<T>::RevealPhaseNotActive);
+			let commitment = Commitments::<T>::get(vote_index, voter_id).ok_or(Error::<T>::CommitmentNotFound)?;
+			ensure!(
+				Self::vote_commitment(vote_index, &voter_id, &candidate_id, &salt) == commitment,
+				Error::<T>::CommitmentMismatch
+			);
+			ensure!(Self::is_ballot_option(vote_index, &candidate_id), Error::<T>::CandidateNotFound);
+
+			Self::add_votes(vote_index, &candidate_id, 1);
+			Commitments::<T>::remove(vote_index, voter_id);
+			SecretTallies::<T>::mutate(vote_index, |tally| tally.revealed = tally.revealed.saturating_add(1));
+
+			Ok(())
+		}
+
+		/// Commitment a voter has to submit for a candidate on a secret ballot. It is bound to
+		/// the session and the voter so a copied commitment cannot be revealed by someone else
+		pub fn vote_commitment(vote_index: VoteIndex, voter_id: &AadhaarId, candidate_id: &AadhaarId, salt: &Salt) -> T::Hash {
+			T::Hashing::hash_of(&(vote_index, voter_id, candidate_id, salt))
+		}
+
 	}
 }
@@ -0,0 +1,80 @@
+use super::*;
+
+/// Membership proofs against the Merkle root of a session's electoral roll
+///
+/// Leaves are the hashes of the eligible aadhaar ids. Every parent hashes its
+/// two children in sorted order, so a proof is just the list of sibling
+/// hashes from the leaf up to the root and needs no left or right flags
+///
+/// The voter set of a session is built in the same format, as a tree of
+/// MaxRollProofDepth levels padded with empty subtrees, so an archived
+/// voter list is checked against the voter root with the same proofs
+impl<T: Config> Pallet<T> {
+
+	/// Leaf of the electoral roll tree for the given aadhaar id
+	pub fn roll_leaf(aadhaar_id: &AadhaarId) -> T::Hash {
+		T::Hashing::hash_of(aadhaar_id)
+	}
+
+	/// Parent node of two nodes of the electoral roll tree
+	pub fn roll_node(a: T::Hash, b: T::Hash) -> T::Hash {
+		if a <= b {
+			T::Hashing::hash_of(&(a, b))
+		} else {
+			T::Hashing::hash_of(&(b, a))
+		}
+	}
+
+	/// Check the proof leads from the aadhaar id to the root
+	pub fn verify_roll_proof(root: &T::Hash, aadhaar_id: &AadhaarId, proof: &[T::Hash]) -> bool {
+		let computed = proof
+			.iter()
+			.fold(Self::roll_leaf(aadhaar_id), |node, sibling| Self::roll_node(node, *sibling));
+		&computed == root
+	}
+
+	/// Append a voter to the voter set tree of the session, a full tree is left unchanged
+	pub(crate) fn append_voter(vote_index: VoteIndex, voter_id: &AadhaarId) {
+		let depth = T::MaxRollProofDepth::get();
+		VoterTrees::<T>::mutate(vote_index, |tree| {
+			let capacity = 1u64.checked_shl(depth).unwrap_or(u64::MAX);
+			if u64::from(tree.count) >= capacity {
+				return
+			}
+
+			// Hash the new leaf up while it is a right child, then keep it as the left node
+			let mut node = Self::roll_leaf(voter_id);
+			let mut size = tree.count.saturating_add(1);
+			for height in 0..depth as usize {
+				if size & 1 == 1 {
+					match tree.branch.get_mut(height) {
+						Some(left) => *left = node,
+						None => {
+							let _ = tree.branch.try_push(node);
+						},
+					}
+					break
+				}
+				node = Self::roll_node(tree.branch[height], node);
+				size >>= 1;
+			}
+			tree.count = tree.count.saturating_add(1);
+		});
+	}
+
+	/// Root of the voter set tree, empty leaves hash to the default hash
+	pub fn voter_set_root(tree: &VoterTree<T>) -> T::Hash {
+		let mut node = T::Hash::default();
+		let mut empty = T::Hash::default();
+		let mut size = tree.count;
+		for height in 0..T::MaxRollProofDepth::get() as usize {
+			node = match (size & 1 == 1, tree.branch.get(height)) {
+				(true, Some(left)) => Self::roll_node(*left, node),
+				_ => Self::roll_node(node, empty),
+			};
+			empty = Self::roll_node(empty, empty);
+			size >>= 1;
+		}
+		node
+	}
+}
@@ -0,0 +1,95 @@
+use super::*;
+
+/// Widens candidate tallies from u16 to VoteCount, straight to the current layout
+pub mod v1 {
+	use super::*;
+
+	#[derive(Decode)]
+	struct OldCandidate {
+		aadhaar_id: AadhaarId,
+		vote_count: u16,
+	}
+
+	pub fn migrate<T: Config>() -> Weight {
+		if Pallet::<T>::on_chain_storage_version() >= 1 {
+			return T::DbWeight::get().reads(1)
+		}
+
+		let mut translated: Weight = 0;
+		Candidates::<T>::translate::<OldCandidate, _>(|_, _, old| {
+			translated = translated.saturating_add(1);
+			Some(Candidate { aadhaar_id: old.aadhaar_id, vote_count: old.vote_count.into() })
+		});
+		// Tallies are already in the v2 layout, there is nothing left for v2 to widen
+		StorageVersion::new(2).put::<Pallet<T>>();
+
+		T::DbWeight::get().reads_writes(translated.saturating_add(1), translated.saturating_add(1))
+	}
+}
+
+/// Widens tallies from u64 to u128 for balance weighted votes
+pub mod v2 {
+	use super::*;
+
+	#[derive(Decode)]
+	struct OldCandidate {
+		aadhaar_id: AadhaarId,
+		vote_count: u64,
+	}
+
+	#[derive(Decode)]
+	struct OldReferendumTally {
+		yes: u64,
+		no: u64,
+		abstain: u64,
+	}
+
+	#[derive(Decode)]
+	struct OldElectionResult {
+		standings: Vec<OldCandidate>,
+		nota: u64,
+		winners: Vec<AadhaarId>,
+		turnout: u32,
+		tied: bool,
+	}
+
+	impl From<OldCandidate> for Candidate {
+		fn from(old: OldCandidate) -> Self {
+			Candidate { aadhaar_id: old.aadhaar_id, vote_count: old.vote_count.into() }
+		}
+	}
+
+	pub fn migrate<T: Config>() -> Weight {
+		if Pallet::<T>::on_chain_storage_version() != 1 {
+			return T::DbWeight::get().reads(1)
+		}
+
+		let mut translated: Weight = 0;
+		Candidates::<T>::translate::<OldCandidate, _>(|_, _, old| {
+			translated = translated.saturating_add(1);
+			Some(old.into())
+		});
+		NotaTallies::<T>::translate::<u64, _>(|_, old| {
+			translated = translated.saturating_add(1);
+			Some(old.into())
+		});
+		ReferendumTallies::<T>::translate::<OldReferendumTally, _>(|_, old| {
+			translated = translated.saturating_add(1);
+			Some(ReferendumTally { yes: old.yes.into(), no: old.no.into(), abstain: old.abstain.into() })
+		});
+		Results::<T>::translate::<OldElectionResult, _>(|_, old| {
+			translated = translated.saturating_add(1);
+			Some(ElectionResult {
+				standings: old.standings.into_iter().map(Into::into).collect(),
+				nota: old.nota.into(),
+				winners: old.winners,
+				turnout: old.turnout,
+				tied: old.tied,
+				referendum: None,
+			})
+		});
+		StorageVersion::new(2).put::<Pallet<T>>();
+
+		T::DbWeight::get().reads_writes(translated.saturating_add(1), translated.saturating_add(1))
+	}
+}
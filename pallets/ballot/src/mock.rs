@@ -1,6 +1,7 @@
 use crate as pallet_ballot;
 
 use frame_support::{
+	parameter_types,
 	traits::{ ConstU16, ConstU32, ConstU64 },
 };
 
@@ -9,7 +10,7 @@ use sp_core::{ H256 };
 use sp_runtime::{
 	testing::Header,
 	traits::{BlakeTwo256, IdentityLookup},
-	BuildStorage,
+	BuildStorage, Perbill,
 };
 use system::EnsureSigned;
 use pallet_aadhaar::types::AadhaarId;
@@ -21,10 +22,17 @@ pub const INITIAL_USER_TWO_AADHAAR: AadhaarId = *b"2222222222222222";
 pub const INITIAL_USER_THREE_ACCOUNT: u64 = 3;
 pub const INITIAL_USER_THREE_AADHAAR: AadhaarId = *b"3333333333333333";
 
+pub const INITIAL_BALANCE: u64 = 1_000;
+pub const NOMINATION_DEPOSIT: u64 = 100;
+
 pub type AccounId = u64;
 type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
 type Block = frame_system::mocking::MockBlock<Test>;
 
+parameter_types! {
+	pub const DepositReturnThreshold: Perbill = Perbill::from_percent(20);
+}
+
 // Configure a mock runtime to test the pallet.
 frame_support::construct_runtime!(
 	pub enum Test where
@@ -33,6 +41,7 @@ frame_support::construct_runtime!(
 		UncheckedExtrinsic = UncheckedExtrinsic,
 	{
 		System: frame_system::{Pallet, Call, Config, Storage, Event<T>},
+		Balances: pallet_balances::{Pallet, Call, Storage, Config<T>, Event<T>},
 		Aadhaar: pallet_aadhaar::{Pallet, Call, Storage, Event<T>, Config<T>},
 		Ballot: pallet_ballot::{Pallet, Call, Storage, Event<T>},
 	}
@@ -56,7 +65,7 @@ impl system::Config for Test {
 	type BlockHashCount = ConstU64<250>;
 	type Version = ();
 	type PalletInfo = PalletInfo;
-	type AccountData = ();
+	type AccountData = pallet_balances::AccountData<u64>;
 	type OnNewAccount = ();
 	type OnKilledAccount = ();
 	type SystemWeightInfo = ();
@@ -65,20 +74,60 @@ impl system::Config for Test {
 	type MaxConsumers = ConstU32<16>;
 }
 
+impl pallet_balances::Config for Test {
+	type MaxLocks = ConstU32<50>;
+	type MaxReserves = ();
+	type ReserveIdentifier = [u8; 8];
+	type Balance = u64;
+	type Event = Event;
+	type DustRemoval = ();
+	type ExistentialDeposit = ConstU64<1>;
+	type AccountStore = System;
+	type WeightInfo = ();
+}
+
 impl pallet_aadhaar::Config for Test {
 	type Event = Event;
 	type RegisterOrigin = EnsureSigned<Self::AccountId>;
+	type MaxConstituencyNameLength = ConstU32<32>;
+	type MaxGuardians = ConstU32<3>;
+	type RecoveryDelay = ConstU64<10>;
 }
 
 impl pallet_ballot::Config for Test {
 	type Event = Event;
 	type ElectionCommissionOrigin = EnsureSigned<Self::AccountId>;
+	type MaxScheduledPerBlock = ConstU32<10>;
+	type MaxBallotChoices = ConstU32<5>;
+	type MaxTallyStepsPerBlock = ConstU32<2>;
+	type MaxProposalLength = ConstU32<64>;
+	type MaxSessionConstituencies = ConstU32<4>;
+	type MaxRollProofDepth = ConstU32<8>;
+	type Currency = Balances;
+	type NominationDeposit = ConstU64<NOMINATION_DEPOSIT>;
+	type DepositReturnThreshold = DepositReturnThreshold;
+	type DepositSlash = ();
+	type MaxNameLength = ConstU32<32>;
+	type MaxManifestoLength = ConstU32<64>;
+	type TieBreakDelay = ConstU64<2>;
+	type MaxDelegatorsPerDelegate = ConstU32<1>;
+	type RetentionPeriod = ConstU64<50>;
+	type MaxCandidatesPerSession = ConstU32<5>;
+	type RepollDelay = ConstU64<5>;
+	type WeightInfo = ();
 }
 
 // Build genesis storage according to the mock runtime.
 pub fn new_test_ext() -> sp_io::TestExternalities {
 	let t = GenesisConfig { 
         system: Default::default(), 
+        balances: pallet_balances::GenesisConfig {
+            balances: vec![
+                (INITIAL_USER_ACCOUNT, INITIAL_BALANCE),
+                (INITIAL_USER_TWO_ACCOUNT, INITIAL_BALANCE),
+                (INITIAL_USER_THREE_ACCOUNT, INITIAL_BALANCE),
+            ],
+        },
         aadhaar: pallet_aadhaar::GenesisConfig {
             initial_aadhaars: vec![(
                 INITIAL_USER_AADHAAR,
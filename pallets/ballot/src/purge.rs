@@ -0,0 +1,107 @@
+use super::*;
+
+/// Lazy removal of the voter data of reset and archived sessions
+///
+/// Such sessions keep a cursor into the voter keyed map being removed.
+/// Each block on_idle spends the weight left over on clear_prefix calls,
+/// resuming from the stored cursor, until every map of the session is
+/// gone. Reset sessions are then marked Idle again, archived sessions
+/// stay Archived
+impl<T: Config> Pallet<T> {
+
+	/// Archive the session once the retention period after its decision passed
+	pub(crate) fn schedule_archive(vote_index: VoteIndex) {
+		let mut archive_at = frame_system::Pallet::<T>::block_number().saturating_add(T::RetentionPeriod::get());
+		// Without room in the schedule the session is archived at the next free block
+		while ScheduledArchives::<T>::try_mutate(archive_at, |queue| queue.try_push(vote_index)).is_err() {
+			archive_at = archive_at.saturating_add(One::one());
+		}
+	}
+
+	/// Commit to the voter set and start pruning the per-voter data of a decided session
+	pub(crate) fn archive_session(vote_index: VoteIndex) {
+		// Reset sessions are purged already
+		if VotingState::<T>::get(vote_index) == VoteState::Purging {
+			return
+		}
+
+		let voter_root = Self::voter_set_root(&VoterTrees::<T>::take(vote_index));
+		VoterRoots::<T>::insert(vote_index, voter_root);
+		VotingState::<T>::insert(vote_index, VoteState::Archived);
+
+		// Candidate tallies live on in the result, referendum tallies are their own result
+		if Results::<T>::contains_key(vote_index) {
+			let _ = Candidates::<T>::clear_prefix(vote_index, T::MaxCandidatesPerSession::get(), None);
+			CandidateCount::<T>::remove(vote_index);
+		}
+		PurgeCursors::<T>::insert(vote_index, PurgeProgress::default());
+
+		Self::deposit_event(Event::SessionArchived { vote_index, voter_root });
+	}
+
+	/// Purge reset sessions within the given weight, returns the weight used
+	pub(crate) fn purge_sessions(remaining_weight: Weight) -> Weight {
+		let db_weight = T::DbWeight::get();
+		// Reading and updating the progress of a session
+		let overhead = db_weight.reads_writes(1, 2);
+		let per_key = db_weight.writes(1).max(1);
+
+		let mut used: Weight = 0;
+		loop {
+			let available = remaining_weight.saturating_sub(used).saturating_sub(overhead);
+			if available < per_key {
+				break
+			}
+			let (vote_index, mut progress) = match PurgeCursors::<T>::iter().next() {
+				Some(entry) => entry,
+				None => break,
+			};
+			let limit = (available / per_key).min(u32::MAX.into()) as u32;
+
+			let (removed, purged) = Self::purge_session(vote_index, &mut progress, limit);
+			used = used.saturating_add(overhead).saturating_add(per_key.saturating_mul(removed.into()));
+
+			if purged {
+				PurgeCursors::<T>::remove(vote_index);
+				if VotingState::<T>::get(vote_index) == VoteState::Purging {
+					VotingState::<T>::remove(vote_index);
+				}
+				Self::deposit_event(Event::SessionPurged { vote_index });
+			} else {
+				PurgeCursors::<T>::insert(vote_index, progress);
+				break
+			}
+		}
+
+		used
+	}
+
+	/// Remove up to limit voter entries of the session, returns entries removed and
+	/// whether the session is gone
+	fn purge_session(vote_index: VoteIndex, progress: &mut PurgeProgress, limit: u32) -> (u32, bool) {
+		let mut removed = 0u32;
+		while removed < limit {
+			let budget = limit.saturating_sub(removed);
+			let cursor = progress.cursor.as_deref();
+			let result = match progress.stage {
+				PurgeStage::Votes => Votes::<T>::clear_prefix(vote_index, budget, cursor),
+				PurgeStage::VotedFor => VotedFor::<T>::clear_prefix(vote_index, budget, cursor),
+				PurgeStage::VoteWeights => VoteWeights::<T>::clear_prefix(vote_index, budget, cursor),
+				PurgeStage::Commitments => Commitments::<T>::clear_prefix(vote_index, budget, cursor),
+				PurgeStage::RankedBallots => RankedBallots::<T>::clear_prefix(vote_index, budget, cursor),
+			};
+			removed = removed.saturating_add(result.loops);
+
+			progress.cursor = result.maybe_cursor;
+			if progress.cursor.is_some() {
+				break
+			}
+			match progress.stage.next() {
+				Some(stage) => progress.stage = stage,
+				None => return (removed, true),
+			}
+		}
+
+		(removed, false)
+	}
+}
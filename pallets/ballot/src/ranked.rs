@@ -0,0 +1,111 @@
+use super::*;
+
+/// Instant-runoff counting for ranked choice sessions
+///
+/// Every round walks the ballots in bounded steps, giving each ballot to its
+/// highest ranked candidate still in the race. Once all ballots are counted
+/// the round is logged and either the leader has a majority of the active
+/// ballots or the last placed candidate is eliminated and a new round begins
+impl<T: Config> Pallet<T> {
+
+	/// Count up to max_ballots ballots of the given session, returns ballots counted
+	pub(crate) fn tally_ranked(vote_index: VoteIndex, max_ballots: u32) -> u32 {
+		let mut progress = match TallyProgress::<T>::get(vote_index) {
+			Some(progress) => progress,
+			None => return 0,
+		};
+
+		let ballot_count = RankedBallotCount::<T>::get(vote_index);
+		let end = progress.cursor.saturating_add(max_ballots).min(ballot_count);
+		for ballot_index in progress.cursor..end {
+			let choice = RankedBallots::<T>::get(vote_index, ballot_index).and_then(|ranking| {
+				ranking.into_iter().find(|candidate_id| !Eliminated::<T>::contains_key(vote_index, candidate_id))
+			});
+			match choice {
+				Some(candidate_id) => RoundCounts::<T>::mutate(vote_index, candidate_id, |votes| {
+					*votes = votes.saturating_add(1)
+				}),
+				None => progress.exhausted = progress.exhausted.saturating_add(1),
+			}
+		}
+
+		let counted = end.saturating_sub(progress.cursor);
+		progress.cursor = end;
+		if progress.cursor < ballot_count {
+			TallyProgress::<T>::insert(vote_index, progress);
+		} else {
+			Self::finish_round(vote_index, progress);
+		}
+
+		counted
+	}
+
+	/// Log a fully counted round and either declare the count complete or eliminate a candidate
+	fn finish_round(vote_index: VoteIndex, progress: RankedTally) {
+		let mut remaining: Vec<(AadhaarId, u32)> = Candidates::<T>::iter_key_prefix(vote_index)
+			.filter(|candidate_id| !Eliminated::<T>::contains_key(vote_index, candidate_id))
+			.map(|candidate_id| (candidate_id, RoundCounts::<T>::get(vote_index, candidate_id)))
+			.collect();
+		remaining.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+		let _ = RoundCounts::<T>::clear_prefix(vote_index, remaining.len() as u32, None);
+
+		let (leader, leader_votes) = match remaining.first() {
+			Some(leader) => *leader,
+			None => return Self::complete_ranked(vote_index, progress.round, &remaining),
+		};
+		let active = RankedBallotCount::<T>::get(vote_index).saturating_sub(progress.exhausted);
+		let has_majority = u64::from(leader_votes) * 2 > u64::from(active);
+
+		if has_majority || remaining.len() == 1 {
+			EliminationLog::<T>::insert(vote_index, progress.round, EliminationRound {
+				leader,
+				leader_votes,
+				eliminated: None,
+				eliminated_votes: 0,
+				exhausted: progress.exhausted,
+			});
+			return Self::complete_ranked(vote_index, progress.round, &remaining)
+		}
+
+		// Last placed candidate is eliminated, ties go to the highest aadhaar id
+		let (eliminated, eliminated_votes) = remaining[remaining.len() - 1];
+		Eliminated::<T>::insert(vote_index, eliminated, progress.round);
+		// Votes only move to remaining candidates, so the last round is the candidate's best
+		Candidates::<T>::mutate(vote_index, eliminated, |candidate| {
+			candidate.vote_count = eliminated_votes.into();
+		});
+		EliminationLog::<T>::insert(vote_index, progress.round, EliminationRound {
+			leader,
+			leader_votes,
+			eliminated: Some(eliminated),
+			eliminated_votes,
+			exhausted: progress.exhausted,
+		});
+		Self::deposit_event(Event::CandidateEliminated {
+			vote_index,
+			round: progress.round,
+			candidate: eliminated,
+			votes: eliminated_votes,
+		});
+
+		TallyProgress::<T>::insert(vote_index, RankedTally {
+			round: progress.round.saturating_add(1),
+			cursor: 0,
+			exhausted: 0,
+		});
+	}
+
+	/// Store the final round counts as tallies of the remaining candidates and end the session
+	fn complete_ranked(vote_index: VoteIndex, rounds: u32, remaining: &[(AadhaarId, u32)]) {
+		for (candidate_id, votes) in remaining {
+			Candidates::<T>::mutate(vote_index, candidate_id, |candidate| {
+				candidate.vote_count = (*votes).into();
+			});
+		}
+		TallyProgress::<T>::remove(vote_index);
+		VotingState::<T>::insert(vote_index, VoteState::Ended);
+
+		Self::deposit_event(Event::TallyCompleted { vote_index, rounds });
+		Self::conclude_session(vote_index);
+	}
+}
@@ -4,6 +4,12 @@ use super::*;
 
 use frame_support::{ assert_ok, assert_noop };
 
+fn run_to_block(n: u64) {
+	while System::block_number() < n {
+		System::set_block_number(System::block_number() + 1);
+		<Ballot as Hooks<u64>>::on_initialize(System::block_number());
+	}
+}
 
 #[test]
 fn test_start_voting() {
@@ -12,6 +18,7 @@ fn test_start_voting() {
 
         assert_ok!(Ballot::start_voting(
 			Origin::signed(INITIAL_USER_ACCOUNT),
+			Default::default(),
 		));
 
 		assert_eq!(VotingState::<Test>::get(vote_index), VoteState::Voting);
@@ -27,6 +34,7 @@ fn test_add_candidates() {
 
         assert_ok!(Ballot::start_voting(
 			Origin::signed(INITIAL_USER_ACCOUNT),
+			Default::default(),
 		));
 
 
@@ -35,7 +43,7 @@ fn test_add_candidates() {
         assert_ok!(Ballot::add_candidates(
 			Origin::signed(INITIAL_USER_ACCOUNT),
             vote_index,
-            candidates,
+            candidates.try_into().unwrap(),
 		));
 
         assert_eq!(Candidates::<Test>::contains_key(vote_index, INITIAL_USER_TWO_AADHAAR), true);
@@ -50,6 +58,7 @@ fn test_vote() {
 
         assert_ok!(Ballot::start_voting(
 			Origin::signed(INITIAL_USER_ACCOUNT),
+			Default::default(),
 		));
 
 
@@ -58,13 +67,14 @@ fn test_vote() {
         assert_ok!(Ballot::add_candidates(
 			Origin::signed(INITIAL_USER_ACCOUNT),
             vote_index,
-            candidates,
+            candidates.try_into().unwrap(),
 		));
 
         assert_ok!(Ballot::vote(
 			Origin::signed(INITIAL_USER_ACCOUNT),
             vote_index,
             INITIAL_USER_TWO_AADHAAR,
+            Default::default(),
 		));
 
         assert_eq!(Candidates::<Test>::get(vote_index, INITIAL_USER_TWO_AADHAAR).vote_count, 1);
@@ -81,6 +91,7 @@ fn test_vote_fails_on_candidate() {
                 Origin::signed(INITIAL_USER_ACCOUNT),
                 vote_index,
                 INITIAL_USER_TWO_AADHAAR,
+                Default::default(),
             ), Error::<Test>::VoteSessionNotFound,
         );
 	})
@@ -93,6 +104,7 @@ fn test_vote_fails_on_duplicate_vote() {
 
         assert_ok!(Ballot::start_voting(
 			Origin::signed(INITIAL_USER_ACCOUNT),
+			Default::default(),
 		));
 
         let candidates = vec![INITIAL_USER_TWO_ACCOUNT, INITIAL_USER_THREE_ACCOUNT];
@@ -100,21 +112,76 @@ fn test_vote_fails_on_duplicate_vote() {
         assert_ok!(Ballot::add_candidates(
 			Origin::signed(INITIAL_USER_ACCOUNT),
             vote_index,
-            candidates,
+            candidates.try_into().unwrap(),
 		));
 
         assert_ok!(Ballot::vote(
 			Origin::signed(INITIAL_USER_ACCOUNT),
             vote_index,
             INITIAL_USER_TWO_AADHAAR,
+            Default::default(),
 		));
 
         assert_noop!(Ballot::vote(
                 Origin::signed(INITIAL_USER_ACCOUNT),
                 vote_index,
                 INITIAL_USER_TWO_AADHAAR,
+                Default::default(),
+		    ), Error::<Test>::VoteAlreadyCast,
+        );
+	})
+}
+
+#[test]
+fn test_rotated_account_cannot_vote_twice() {
+	new_test_ext().execute_with(|| {
+        let vote_index = 0;
+        let new_account_id = 4;
+
+        assert_ok!(Ballot::start_voting(
+			Origin::signed(INITIAL_USER_ACCOUNT),
+			Default::default(),
+		));
+
+        let candidates = vec![INITIAL_USER_TWO_ACCOUNT, INITIAL_USER_THREE_ACCOUNT];
+
+        assert_ok!(Ballot::add_candidates(
+			Origin::signed(INITIAL_USER_ACCOUNT),
+            vote_index,
+            candidates.try_into().unwrap(),
+		));
+
+        assert_ok!(Ballot::vote(
+			Origin::signed(INITIAL_USER_ACCOUNT),
+            vote_index,
+            INITIAL_USER_TWO_AADHAAR,
+            Default::default(),
+		));
+
+        // Votes are recorded per aadhaar, the rotated account casts for the same voter
+        assert_ok!(Aadhaar::rotate_account(
+			Origin::signed(INITIAL_USER_ACCOUNT),
+            INITIAL_USER_AADHAAR,
+            new_account_id,
+		));
+
+        assert_noop!(Ballot::vote(
+                Origin::signed(new_account_id),
+                vote_index,
+                INITIAL_USER_THREE_AADHAAR,
+                Default::default(),
 		    ), Error::<Test>::VoteAlreadyCast,
         );
+        assert_noop!(Ballot::vote(
+                Origin::signed(INITIAL_USER_ACCOUNT),
+                vote_index,
+                INITIAL_USER_THREE_AADHAAR,
+                Default::default(),
+		    ), Error::<Test>::VoterNotRegistered,
+        );
+
+        assert_eq!(Candidates::<Test>::get(vote_index, INITIAL_USER_TWO_AADHAAR).vote_count, 1);
+        assert_eq!(Candidates::<Test>::get(vote_index, INITIAL_USER_THREE_AADHAAR).vote_count, 0);
 	})
 }
 
@@ -124,6 +191,7 @@ fn test_stop_voting() {
 
         assert_ok!(Ballot::start_voting(
 			Origin::signed(INITIAL_USER_ACCOUNT),
+			Default::default(),
 		));
 
         let vote_index = 0;
@@ -140,21 +208,1994 @@ fn test_stop_voting() {
 #[test]
 fn test_reset_voting() {
 	new_test_ext().execute_with(|| {
+        System::set_block_number(1);
 
         assert_ok!(Ballot::start_voting(
 			Origin::signed(INITIAL_USER_ACCOUNT),
+			Default::default(),
 		));
 
         let vote_index = 0;
 
+        assert_ok!(Ballot::add_candidates(
+			Origin::signed(INITIAL_USER_ACCOUNT),
+            vote_index,
+            vec![INITIAL_USER_TWO_ACCOUNT, INITIAL_USER_THREE_ACCOUNT].try_into().unwrap(),
+		));
+        for voter in [INITIAL_USER_ACCOUNT, INITIAL_USER_TWO_ACCOUNT, INITIAL_USER_THREE_ACCOUNT] {
+            assert_ok!(Ballot::vote(
+                Origin::signed(voter),
+                vote_index,
+                INITIAL_USER_TWO_AADHAAR,
+                Default::default(),
+            ));
+        }
+
         assert_ok!(Ballot::reset_voting(
 			Origin::signed(INITIAL_USER_ACCOUNT),
             vote_index,
 		));
+        System::assert_last_event(crate::mock::Event::Ballot(crate::Event::VotingReset {
+            aadhaar_id: INITIAL_USER_AADHAAR,
+            vote_index,
+        }));
 
-		assert_eq!(VotingState::<Test>::get(vote_index), VoteState::Idle);
+        // Candidates are gone at once, votes are purged in on_idle
+		assert_eq!(VotingState::<Test>::get(vote_index), VoteState::Purging);
 		assert_eq!(ChiefCommissioner::<Test>::get(vote_index), None);
 		assert_eq!(!Candidates::<Test>::contains_key(vote_index, INITIAL_USER_TWO_AADHAAR), true);
-		assert_eq!(!Votes::<Test>::contains_key(vote_index, INITIAL_USER_AADHAAR), true);
+        assert_eq!(Votes::<Test>::iter_prefix(vote_index).count(), 3);
+        assert_noop!(
+            Ballot::reset_voting(Origin::signed(INITIAL_USER_ACCOUNT), vote_index),
+            Error::<Test>::SessionPurging,
+        );
+        // Sessions not opened yet cannot be reset
+        assert_noop!(
+            Ballot::reset_voting(Origin::signed(INITIAL_USER_ACCOUNT), vote_index + 1),
+            Error::<Test>::VoteSessionNotFound,
+        );
+        assert_eq!(VotingState::<Test>::get(vote_index + 1), VoteState::Idle);
+
+        // Mock writes weigh nothing, so each unit of weight removes one entry
+        <Ballot as Hooks<u64>>::on_idle(1, 2);
+        assert_eq!(Votes::<Test>::iter_prefix(vote_index).count(), 1);
+		assert_eq!(VotingState::<Test>::get(vote_index), VoteState::Purging);
+        assert!(PurgeCursors::<Test>::get(vote_index).unwrap().cursor.is_some());
+
+        <Ballot as Hooks<u64>>::on_idle(2, 100);
+        assert_eq!(Votes::<Test>::iter_prefix(vote_index).count(), 0);
+		assert_eq!(VotingState::<Test>::get(vote_index), VoteState::Idle);
+        assert_eq!(PurgeCursors::<Test>::get(vote_index), None);
+        System::assert_last_event(crate::mock::Event::Ballot(crate::Event::SessionPurged { vote_index }));
+        assert_noop!(
+            Ballot::reset_voting(Origin::signed(INITIAL_USER_ACCOUNT), vote_index),
+            Error::<Test>::VoteSessionNotFound,
+        );
+	})
+}
+
+#[test]
+fn test_reset_purge_resumes_across_maps() {
+	new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let vote_index = 0;
+
+        assert_ok!(Ballot::start_voting(
+			Origin::signed(INITIAL_USER_ACCOUNT),
+			SessionConfig { allow_recast: true, ..Default::default() },
+		));
+        assert_ok!(Ballot::add_candidates(
+			Origin::signed(INITIAL_USER_ACCOUNT),
+            vote_index,
+            vec![INITIAL_USER_TWO_ACCOUNT, INITIAL_USER_THREE_ACCOUNT].try_into().unwrap(),
+		));
+        for voter in [INITIAL_USER_ACCOUNT, INITIAL_USER_TWO_ACCOUNT, INITIAL_USER_THREE_ACCOUNT] {
+            assert_ok!(Ballot::vote(Origin::signed(voter), vote_index, INITIAL_USER_TWO_AADHAAR, Default::default()));
+        }
+        // A session only fills some of the voter maps, fill the others by hand
+        let ranking: BoundedVec<AadhaarId, <Test as crate::Config>::MaxBallotChoices> =
+            vec![INITIAL_USER_TWO_AADHAAR].try_into().unwrap();
+        for index in 0..3u8 {
+            Commitments::<Test>::insert(vote_index, [index; 16], sp_core::H256::repeat_byte(index));
+            RankedBallots::<Test>::insert(vote_index, index as u32, ranking.clone());
+        }
+
+        assert_ok!(Ballot::reset_voting(Origin::signed(INITIAL_USER_ACCOUNT), vote_index));
+
+        // Votes are gone and the cursor stops inside VotedFor
+        <Ballot as Hooks<u64>>::on_idle(1, 4);
+        assert_eq!(Votes::<Test>::iter_prefix(vote_index).count(), 0);
+        assert_eq!(VotedFor::<Test>::iter_prefix(vote_index).count(), 2);
+        let progress = PurgeCursors::<Test>::get(vote_index).unwrap();
+        assert_eq!(progress.stage, PurgeStage::VotedFor);
+        assert!(progress.cursor.is_some());
+
+        // It resumes there, skips the empty VoteWeights and stops inside Commitments
+        <Ballot as Hooks<u64>>::on_idle(2, 4);
+        assert_eq!(VotedFor::<Test>::iter_prefix(vote_index).count(), 0);
+        assert_eq!(Commitments::<Test>::iter_prefix(vote_index).count(), 1);
+        assert_eq!(RankedBallots::<Test>::iter_prefix(vote_index).count(), 3);
+        let progress = PurgeCursors::<Test>::get(vote_index).unwrap();
+        assert_eq!(progress.stage, PurgeStage::Commitments);
+        assert!(progress.cursor.is_some());
+
+        <Ballot as Hooks<u64>>::on_idle(3, 100);
+        assert_eq!(Commitments::<Test>::iter_prefix(vote_index).count(), 0);
+        assert_eq!(RankedBallots::<Test>::iter_prefix(vote_index).count(), 0);
+        assert_eq!(PurgeCursors::<Test>::get(vote_index), None);
+		assert_eq!(VotingState::<Test>::get(vote_index), VoteState::Idle);
+        System::assert_last_event(crate::mock::Event::Ballot(crate::Event::SessionPurged { vote_index }));
+	})
+}
+
+#[test]
+fn test_decided_session_archived_after_retention() {
+	new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let vote_index = 0;
+        let voters = [INITIAL_USER_AADHAAR, INITIAL_USER_TWO_AADHAAR, INITIAL_USER_THREE_AADHAAR];
+
+        assert_ok!(Ballot::start_voting(
+			Origin::signed(INITIAL_USER_ACCOUNT),
+			Default::default(),
+		));
+        assert_ok!(Ballot::add_candidates(
+			Origin::signed(INITIAL_USER_ACCOUNT),
+            vote_index,
+            vec![INITIAL_USER_TWO_ACCOUNT, INITIAL_USER_THREE_ACCOUNT].try_into().unwrap(),
+		));
+        for voter in [INITIAL_USER_ACCOUNT, INITIAL_USER_TWO_ACCOUNT, INITIAL_USER_THREE_ACCOUNT] {
+            assert_ok!(Ballot::vote(
+                Origin::signed(voter),
+                vote_index,
+                INITIAL_USER_TWO_AADHAAR,
+                Default::default(),
+            ));
+        }
+        assert_ok!(Ballot::stop_voting(Origin::signed(INITIAL_USER_ACCOUNT), vote_index));
+        let result = Results::<Test>::get(vote_index).unwrap();
+
+        // Per-voter data is kept for the retention period
+        run_to_block(50);
+        assert_eq!(VotingState::<Test>::get(vote_index), VoteState::Ended);
+        assert_eq!(VoterRoots::<Test>::get(vote_index), None);
+
+        run_to_block(51);
+        assert_eq!(VotingState::<Test>::get(vote_index), VoteState::Archived);
+        let voter_root = VoterRoots::<Test>::get(vote_index).unwrap();
+        System::assert_last_event(crate::mock::Event::Ballot(crate::Event::SessionArchived { vote_index, voter_root }));
+        assert_eq!(Candidates::<Test>::iter_prefix(vote_index).count(), 0);
+
+        <Ballot as Hooks<u64>>::on_idle(51, 100);
+        assert_eq!(Votes::<Test>::iter_prefix(vote_index).count(), 0);
+        assert_eq!(VotingState::<Test>::get(vote_index), VoteState::Archived);
+        assert_eq!(Results::<Test>::get(vote_index), Some(result));
+
+        // The archived voter list, in voting order, is checked against the root
+        let leaves: Vec<_> = voters.iter().map(Ballot::roll_leaf).collect();
+        let mut empty = sp_core::H256::default();
+        let mut proof = vec![leaves[1], Ballot::roll_node(leaves[2], empty)];
+        empty = Ballot::roll_node(empty, empty);
+        for _ in 2..8 {
+            empty = Ballot::roll_node(empty, empty);
+            proof.push(empty);
+        }
+        assert!(Ballot::verify_roll_proof(&voter_root, &INITIAL_USER_AADHAAR, &proof));
+        assert!(!Ballot::verify_roll_proof(&voter_root, &INITIAL_USER_TWO_AADHAAR, &proof));
+	})
+}
+
+#[test]
+fn test_secret_ballot_commit_and_reveal() {
+	new_test_ext().execute_with(|| {
+        let vote_index = 0;
+        let salt: Salt = [7; 32];
+
+        assert_ok!(Ballot::start_voting(
+			Origin::signed(INITIAL_USER_ACCOUNT),
+			SessionConfig { secret: true, ..Default::default() },
+		));
+		assert_eq!(VotingState::<Test>::get(vote_index), VoteState::Committing);
+
+        assert_ok!(Ballot::add_candidates(
+			Origin::signed(INITIAL_USER_ACCOUNT),
+            vote_index,
+            vec![INITIAL_USER_TWO_ACCOUNT, INITIAL_USER_THREE_ACCOUNT].try_into().unwrap(),
+		));
+
+        assert_ok!(Ballot::commit_vote(
+			Origin::signed(INITIAL_USER_ACCOUNT),
+            vote_index,
+            Ballot::vote_commitment(vote_index, &INITIAL_USER_AADHAAR, &INITIAL_USER_TWO_AADHAAR, &salt),
+            Default::default(),
+		));
+
+        // Nothing is counted while commitments are being collected
+        assert_eq!(Candidates::<Test>::get(vote_index, INITIAL_USER_TWO_AADHAAR).vote_count, 0);
+        assert_noop!(Ballot::reveal_vote(
+                Origin::signed(INITIAL_USER_ACCOUNT),
+                vote_index,
+                INITIAL_USER_TWO_AADHAAR,
+                salt,
+            ), Error::<Test>::RevealPhaseNotActive,
+        );
+
+        assert_ok!(Ballot::stop_voting(Origin::signed(INITIAL_USER_ACCOUNT), vote_index));
+		assert_eq!(VotingState::<Test>::get(vote_index), VoteState::Revealing);
+
+        assert_ok!(Ballot::reveal_vote(
+			Origin::signed(INITIAL_USER_ACCOUNT),
+            vote_index,
+            INITIAL_USER_TWO_AADHAAR,
+            salt,
+		));
+
+        assert_eq!(Candidates::<Test>::get(vote_index, INITIAL_USER_TWO_AADHAAR).vote_count, 1);
+        assert_eq!(Commitments::<Test>::contains_key(vote_index, INITIAL_USER_AADHAAR), false);
+
+        assert_ok!(Ballot::stop_voting(Origin::signed(INITIAL_USER_ACCOUNT), vote_index));
+		assert_eq!(VotingState::<Test>::get(vote_index), VoteState::Ended);
+	})
+}
+
+#[test]
+fn test_secret_ballot_rejects_plain_vote_and_bad_reveal() {
+	new_test_ext().execute_with(|| {
+        let vote_index = 0;
+
+        assert_ok!(Ballot::start_voting(
+			Origin::signed(INITIAL_USER_ACCOUNT),
+			SessionConfig { secret: true, ..Default::default() },
+		));
+
+        assert_ok!(Ballot::add_candidates(
+			Origin::signed(INITIAL_USER_ACCOUNT),
+            vote_index,
+            vec![INITIAL_USER_TWO_ACCOUNT, INITIAL_USER_THREE_ACCOUNT].try_into().unwrap(),
+		));
+
+        assert_noop!(Ballot::vote(
+                Origin::signed(INITIAL_USER_ACCOUNT),
+                vote_index,
+                INITIAL_USER_TWO_AADHAAR,
+                Default::default(),
+            ), Error::<Test>::VotingNotActive,
+        );
+
+        let commitment = Ballot::vote_commitment(vote_index, &INITIAL_USER_AADHAAR, &INITIAL_USER_TWO_AADHAAR, &[1; 32]);
+        assert_ok!(Ballot::commit_vote(
+			Origin::signed(INITIAL_USER_ACCOUNT),
+            vote_index,
+            commitment,
+            Default::default(),
+		));
+        // Another voter copies the commitment to replay its reveal
+        assert_ok!(Ballot::commit_vote(
+			Origin::signed(INITIAL_USER_THREE_ACCOUNT),
+            vote_index,
+            commitment,
+            Default::default(),
+		));
+        assert_ok!(Ballot::stop_voting(Origin::signed(INITIAL_USER_ACCOUNT), vote_index));
+
+        assert_noop!(Ballot::reveal_vote(
+                Origin::signed(INITIAL_USER_ACCOUNT),
+                vote_index,
+                INITIAL_USER_THREE_AADHAAR,
+                [1; 32],
+            ), Error::<Test>::CommitmentMismatch,
+        );
+        assert_ok!(Ballot::reveal_vote(
+			Origin::signed(INITIAL_USER_ACCOUNT),
+            vote_index,
+            INITIAL_USER_TWO_AADHAAR,
+            [1; 32],
+		));
+        assert_noop!(Ballot::reveal_vote(
+                Origin::signed(INITIAL_USER_THREE_ACCOUNT),
+                vote_index,
+                INITIAL_USER_TWO_AADHAAR,
+                [1; 32],
+            ), Error::<Test>::CommitmentMismatch,
+        );
+	})
+}
+
+#[test]
+fn test_secret_ballot_reports_unrevealed_commitments() {
+	new_test_ext().execute_with(|| {
+        let vote_index = 0;
+        System::set_block_number(1);
+
+        assert_ok!(Ballot::start_voting(
+			Origin::signed(INITIAL_USER_ACCOUNT),
+			SessionConfig { secret: true, ..Default::default() },
+		));
+
+        assert_ok!(Ballot::add_candidates(
+			Origin::signed(INITIAL_USER_ACCOUNT),
+            vote_index,
+            vec![INITIAL_USER_TWO_ACCOUNT].try_into().unwrap(),
+		));
+
+        assert_ok!(Ballot::commit_vote(
+			Origin::signed(INITIAL_USER_THREE_ACCOUNT),
+            vote_index,
+            Ballot::vote_commitment(vote_index, &INITIAL_USER_THREE_AADHAAR, &INITIAL_USER_TWO_AADHAAR, &[3; 32]),
+            Default::default(),
+		));
+
+        assert_ok!(Ballot::stop_voting(Origin::signed(INITIAL_USER_ACCOUNT), vote_index));
+        assert_ok!(Ballot::stop_voting(Origin::signed(INITIAL_USER_ACCOUNT), vote_index));
+
+        assert_eq!(SecretTallies::<Test>::get(vote_index).unrevealed(), 1);
+        assert_eq!(Candidates::<Test>::get(vote_index, INITIAL_USER_TWO_AADHAAR).vote_count, 0);
+        System::assert_has_event(
+            crate::mock::Event::Ballot(crate::Event::CommitmentsUnrevealed { vote_index, count: 1 })
+        );
+	})
+}
+
+#[test]
+fn test_scheduled_voting_opens_and_closes() {
+	new_test_ext().execute_with(|| {
+        let vote_index = 0;
+        System::set_block_number(1);
+
+        assert_ok!(Ballot::start_voting(
+			Origin::signed(INITIAL_USER_ACCOUNT),
+			SessionConfig { starts_at: Some(5), ends_at: Some(10), ..Default::default() },
+		));
+		assert_eq!(VotingState::<Test>::get(vote_index), VoteState::Idle);
+
+        run_to_block(5);
+		assert_eq!(VotingState::<Test>::get(vote_index), VoteState::Voting);
+        System::assert_last_event(
+            crate::mock::Event::Ballot(crate::Event::VotingStarted { aadhaar_id: INITIAL_USER_AADHAAR, vote_index })
+        );
+
+        run_to_block(10);
+		assert_eq!(VotingState::<Test>::get(vote_index), VoteState::Ended);
+        System::assert_last_event(
+            crate::mock::Event::Ballot(crate::Event::VotingStopped { aadhaar_id: INITIAL_USER_AADHAAR, vote_index })
+        );
+	})
+}
+
+#[test]
+fn test_scheduled_voting_can_be_stopped_early() {
+	new_test_ext().execute_with(|| {
+        let vote_index = 0;
+        System::set_block_number(1);
+
+        assert_ok!(Ballot::start_voting(
+			Origin::signed(INITIAL_USER_ACCOUNT),
+			SessionConfig { ends_at: Some(10), ..Default::default() },
+		));
+		assert_eq!(VotingState::<Test>::get(vote_index), VoteState::Voting);
+
+        assert_ok!(Ballot::stop_voting(Origin::signed(INITIAL_USER_ACCOUNT), vote_index));
+		assert_eq!(VotingState::<Test>::get(vote_index), VoteState::Ended);
+
+        // The scheduled close is a no-op once the session has ended
+        run_to_block(10);
+		assert_eq!(VotingState::<Test>::get(vote_index), VoteState::Ended);
+	})
+}
+
+#[test]
+fn test_scheduled_secret_ballot_closes_reveals() {
+	new_test_ext().execute_with(|| {
+        let vote_index = 0;
+        let salt: Salt = [5; 32];
+        System::set_block_number(1);
+
+        assert_noop!(Ballot::start_voting(
+                Origin::signed(INITIAL_USER_ACCOUNT),
+                SessionConfig { starts_at: Some(5), ends_at: Some(10), reveal_ends_at: Some(15), ..Default::default() },
+            ), Error::<Test>::InvalidSchedule,
+        );
+        assert_noop!(Ballot::start_voting(
+                Origin::signed(INITIAL_USER_ACCOUNT),
+                SessionConfig { secret: true, ends_at: Some(10), reveal_ends_at: Some(10), ..Default::default() },
+            ), Error::<Test>::InvalidSchedule,
+        );
+
+        assert_ok!(Ballot::start_voting(
+			Origin::signed(INITIAL_USER_ACCOUNT),
+			SessionConfig {
+                secret: true,
+                starts_at: Some(5),
+                ends_at: Some(10),
+                reveal_ends_at: Some(15),
+                ..Default::default()
+            },
+		));
+        assert_ok!(Ballot::add_candidates(
+			Origin::signed(INITIAL_USER_ACCOUNT),
+            vote_index,
+            vec![INITIAL_USER_TWO_ACCOUNT].try_into().unwrap(),
+		));
+
+        run_to_block(5);
+		assert_eq!(VotingState::<Test>::get(vote_index), VoteState::Committing);
+        assert_ok!(Ballot::commit_vote(
+			Origin::signed(INITIAL_USER_ACCOUNT),
+            vote_index,
+            Ballot::vote_commitment(vote_index, &INITIAL_USER_AADHAAR, &INITIAL_USER_TWO_AADHAAR, &salt),
+            Default::default(),
+		));
+
+        run_to_block(10);
+		assert_eq!(VotingState::<Test>::get(vote_index), VoteState::Revealing);
+        assert_ok!(Ballot::reveal_vote(
+			Origin::signed(INITIAL_USER_ACCOUNT),
+            vote_index,
+            INITIAL_USER_TWO_AADHAAR,
+            salt,
+		));
+
+        run_to_block(15);
+		assert_eq!(VotingState::<Test>::get(vote_index), VoteState::Ended);
+        assert_eq!(Ballot::results(vote_index).unwrap().winners, vec![INITIAL_USER_TWO_AADHAAR]);
+	})
+}
+
+#[test]
+fn test_start_voting_fails_on_invalid_schedule() {
+	new_test_ext().execute_with(|| {
+        System::set_block_number(5);
+
+        assert_noop!(Ballot::start_voting(
+                Origin::signed(INITIAL_USER_ACCOUNT),
+                SessionConfig { starts_at: Some(5), ..Default::default() },
+            ), Error::<Test>::InvalidSchedule,
+        );
+
+        assert_noop!(Ballot::start_voting(
+                Origin::signed(INITIAL_USER_ACCOUNT),
+                SessionConfig { starts_at: Some(8), ends_at: Some(8), ..Default::default() },
+            ), Error::<Test>::InvalidSchedule,
+        );
+	})
+}
+
+#[test]
+fn test_runoff_started_without_majority() {
+	new_test_ext().execute_with(|| {
+        let vote_index = 0;
+        System::set_block_number(1);
+
+        assert_ok!(Ballot::start_voting(
+			Origin::signed(INITIAL_USER_ACCOUNT),
+			SessionConfig { majority: Some(Perbill::from_percent(50)), runoff_size: 2, ..Default::default() },
+		));
+
+        assert_ok!(Ballot::add_candidates(
+			Origin::signed(INITIAL_USER_ACCOUNT),
+            vote_index,
+            vec![INITIAL_USER_ACCOUNT, INITIAL_USER_TWO_ACCOUNT, INITIAL_USER_THREE_ACCOUNT].try_into().unwrap(),
+		));
+
+        assert_ok!(Ballot::vote(Origin::signed(INITIAL_USER_ACCOUNT), vote_index, INITIAL_USER_AADHAAR, Default::default()));
+        assert_ok!(Ballot::vote(Origin::signed(INITIAL_USER_TWO_ACCOUNT), vote_index, INITIAL_USER_TWO_AADHAAR, Default::default()));
+        assert_ok!(Ballot::vote(Origin::signed(INITIAL_USER_THREE_ACCOUNT), vote_index, INITIAL_USER_THREE_AADHAAR, Default::default()));
+
+        assert_ok!(Ballot::stop_voting(Origin::signed(INITIAL_USER_ACCOUNT), vote_index));
+
+        let runoff_index = 1;
+		assert_eq!(RunoffOf::<Test>::get(vote_index), Some(runoff_index));
+		assert_eq!(ParentSession::<Test>::get(runoff_index), Some(vote_index));
+		assert_eq!(VotingState::<Test>::get(runoff_index), VoteState::Voting);
+        assert_eq!(Candidates::<Test>::contains_key(runoff_index, INITIAL_USER_AADHAAR), true);
+        assert_eq!(Candidates::<Test>::contains_key(runoff_index, INITIAL_USER_TWO_AADHAAR), true);
+        assert_eq!(Candidates::<Test>::contains_key(runoff_index, INITIAL_USER_THREE_AADHAAR), false);
+
+        // The runoff is decided and reported against the first round
+        assert_ok!(Ballot::vote(Origin::signed(INITIAL_USER_ACCOUNT), runoff_index, INITIAL_USER_TWO_AADHAAR, Default::default()));
+        assert_ok!(Ballot::stop_voting(Origin::signed(INITIAL_USER_ACCOUNT), runoff_index));
+
+        System::assert_has_event(crate::mock::Event::Ballot(crate::Event::ElectionDecided {
+            vote_index: runoff_index,
+            first_round: vote_index,
+            winner: Some(INITIAL_USER_TWO_AADHAAR),
+        }));
+	})
+}
+
+#[test]
+fn test_majority_winner_decides_session() {
+	new_test_ext().execute_with(|| {
+        let vote_index = 0;
+        System::set_block_number(1);
+
+        assert_ok!(Ballot::start_voting(
+			Origin::signed(INITIAL_USER_ACCOUNT),
+			SessionConfig { majority: Some(Perbill::from_percent(50)), runoff_size: 2, ..Default::default() },
+		));
+
+        assert_ok!(Ballot::add_candidates(
+			Origin::signed(INITIAL_USER_ACCOUNT),
+            vote_index,
+            vec![INITIAL_USER_ACCOUNT, INITIAL_USER_TWO_ACCOUNT, INITIAL_USER_THREE_ACCOUNT].try_into().unwrap(),
+		));
+
+        assert_ok!(Ballot::vote(Origin::signed(INITIAL_USER_ACCOUNT), vote_index, INITIAL_USER_TWO_AADHAAR, Default::default()));
+        assert_ok!(Ballot::vote(Origin::signed(INITIAL_USER_TWO_ACCOUNT), vote_index, INITIAL_USER_TWO_AADHAAR, Default::default()));
+        assert_ok!(Ballot::vote(Origin::signed(INITIAL_USER_THREE_ACCOUNT), vote_index, INITIAL_USER_THREE_AADHAAR, Default::default()));
+
+        assert_ok!(Ballot::stop_voting(Origin::signed(INITIAL_USER_ACCOUNT), vote_index));
+
+		assert_eq!(RunoffOf::<Test>::get(vote_index), None);
+        System::assert_last_event(crate::mock::Event::Ballot(crate::Event::ElectionDecided {
+            vote_index,
+            first_round: vote_index,
+            winner: Some(INITIAL_USER_TWO_AADHAAR),
+        }));
+	})
+}
+
+fn ranked(ranking: Vec<AadhaarId>) -> BallotChoice<Test> {
+	BallotChoice::Ranked(ranking.try_into().unwrap())
+}
+
+#[test]
+fn test_ranked_choice_instant_runoff() {
+	new_test_ext().execute_with(|| {
+        let vote_index = 0;
+        System::set_block_number(1);
+
+        assert_ok!(Ballot::start_voting(
+			Origin::signed(INITIAL_USER_ACCOUNT),
+			SessionConfig { ballot_type: BallotType::RankedChoice, ..Default::default() },
+		));
+
+        assert_ok!(Ballot::add_candidates(
+			Origin::signed(INITIAL_USER_ACCOUNT),
+            vote_index,
+            vec![INITIAL_USER_ACCOUNT, INITIAL_USER_TWO_ACCOUNT, INITIAL_USER_THREE_ACCOUNT].try_into().unwrap(),
+		));
+
+        assert_ok!(Ballot::cast_ballot(
+			Origin::signed(INITIAL_USER_ACCOUNT),
+            vote_index,
+            ranked(vec![INITIAL_USER_THREE_AADHAAR, INITIAL_USER_TWO_AADHAAR]),
+            Default::default(),
+		));
+        assert_ok!(Ballot::cast_ballot(
+			Origin::signed(INITIAL_USER_TWO_ACCOUNT),
+            vote_index,
+            ranked(vec![INITIAL_USER_TWO_AADHAAR, INITIAL_USER_THREE_AADHAAR]),
+            Default::default(),
+		));
+        assert_ok!(Ballot::cast_ballot(
+			Origin::signed(INITIAL_USER_THREE_ACCOUNT),
+            vote_index,
+            ranked(vec![INITIAL_USER_AADHAAR, INITIAL_USER_TWO_AADHAAR]),
+            Default::default(),
+		));
+
+        assert_ok!(Ballot::stop_voting(Origin::signed(INITIAL_USER_ACCOUNT), vote_index));
+		assert_eq!(VotingState::<Test>::get(vote_index), VoteState::Tallying);
+
+        // Two ballots are counted per block, so each round takes two blocks
+        run_to_block(3);
+		assert_eq!(Eliminated::<Test>::get(vote_index, INITIAL_USER_THREE_AADHAAR), Some(1));
+		assert_eq!(VotingState::<Test>::get(vote_index), VoteState::Tallying);
+
+        run_to_block(5);
+		assert_eq!(VotingState::<Test>::get(vote_index), VoteState::Ended);
+		assert_eq!(EliminationLog::<Test>::get(vote_index, 2), Some(EliminationRound {
+            leader: INITIAL_USER_TWO_AADHAAR,
+            leader_votes: 2,
+            eliminated: None,
+            eliminated_votes: 0,
+            exhausted: 0,
+        }));
+        assert_eq!(Candidates::<Test>::get(vote_index, INITIAL_USER_TWO_AADHAAR).vote_count, 2);
+        // Eliminated candidates keep the votes of the round they were eliminated in
+        assert_eq!(Candidates::<Test>::get(vote_index, INITIAL_USER_THREE_AADHAAR).vote_count, 1);
+        System::assert_has_event(crate::mock::Event::Ballot(crate::Event::ElectionDecided {
+            vote_index,
+            first_round: vote_index,
+            winner: Some(INITIAL_USER_TWO_AADHAAR),
+        }));
+	})
+}
+
+#[test]
+fn test_ranked_choice_rejects_invalid_ballots() {
+	new_test_ext().execute_with(|| {
+        let vote_index = 0;
+
+        assert_ok!(Ballot::start_voting(
+			Origin::signed(INITIAL_USER_ACCOUNT),
+			SessionConfig { ballot_type: BallotType::RankedChoice, ..Default::default() },
+		));
+
+        assert_ok!(Ballot::add_candidates(
+			Origin::signed(INITIAL_USER_ACCOUNT),
+            vote_index,
+            vec![INITIAL_USER_TWO_ACCOUNT, INITIAL_USER_THREE_ACCOUNT].try_into().unwrap(),
+		));
+
+        assert_noop!(Ballot::vote(
+                Origin::signed(INITIAL_USER_ACCOUNT),
+                vote_index,
+                INITIAL_USER_TWO_AADHAAR,
+                Default::default(),
+            ), Error::<Test>::BallotTypeMismatch,
+        );
+
+        assert_noop!(Ballot::cast_ballot(
+                Origin::signed(INITIAL_USER_ACCOUNT),
+                vote_index,
+                ranked(vec![INITIAL_USER_TWO_AADHAAR, INITIAL_USER_TWO_AADHAAR]),
+                Default::default(),
+            ), Error::<Test>::DuplicateChoice,
+        );
+
+        assert_noop!(Ballot::cast_ballot(
+                Origin::signed(INITIAL_USER_ACCOUNT),
+                vote_index,
+                ranked(vec![INITIAL_USER_AADHAAR]),
+                Default::default(),
+            ), Error::<Test>::CandidateNotFound,
+        );
+	})
+}
+
+#[test]
+fn test_approval_voting() {
+	new_test_ext().execute_with(|| {
+        let vote_index = 0;
+
+        assert_ok!(Ballot::start_voting(
+			Origin::signed(INITIAL_USER_ACCOUNT),
+			SessionConfig { ballot_type: BallotType::Approval, ..Default::default() },
+		));
+
+        assert_ok!(Ballot::add_candidates(
+			Origin::signed(INITIAL_USER_ACCOUNT),
+            vote_index,
+            vec![INITIAL_USER_TWO_ACCOUNT, INITIAL_USER_THREE_ACCOUNT].try_into().unwrap(),
+		));
+
+        assert_ok!(Ballot::cast_ballot(
+			Origin::signed(INITIAL_USER_ACCOUNT),
+            vote_index,
+            BallotChoice::Approval(vec![INITIAL_USER_TWO_AADHAAR, INITIAL_USER_THREE_AADHAAR].try_into().unwrap()),
+            Default::default(),
+		));
+        assert_ok!(Ballot::cast_ballot(
+			Origin::signed(INITIAL_USER_TWO_ACCOUNT),
+            vote_index,
+            BallotChoice::Approval(vec![INITIAL_USER_TWO_AADHAAR].try_into().unwrap()),
+            Default::default(),
+		));
+
+        assert_eq!(Candidates::<Test>::get(vote_index, INITIAL_USER_TWO_AADHAAR).vote_count, 2);
+        assert_eq!(Candidates::<Test>::get(vote_index, INITIAL_USER_THREE_AADHAAR).vote_count, 1);
+
+        assert_noop!(Ballot::cast_ballot(
+                Origin::signed(INITIAL_USER_ACCOUNT),
+                vote_index,
+                BallotChoice::Approval(vec![INITIAL_USER_THREE_AADHAAR].try_into().unwrap()),
+                Default::default(),
+            ), Error::<Test>::VoteAlreadyCast,
+        );
+	})
+}
+
+#[test]
+fn test_score_voting() {
+	new_test_ext().execute_with(|| {
+        let vote_index = 0;
+
+        assert_ok!(Ballot::start_voting(
+			Origin::signed(INITIAL_USER_ACCOUNT),
+			SessionConfig { ballot_type: BallotType::Score { max_score: 5 }, ..Default::default() },
+		));
+
+        assert_ok!(Ballot::add_candidates(
+			Origin::signed(INITIAL_USER_ACCOUNT),
+            vote_index,
+            vec![INITIAL_USER_TWO_ACCOUNT, INITIAL_USER_THREE_ACCOUNT].try_into().unwrap(),
+		));
+
+        assert_noop!(Ballot::cast_ballot(
+                Origin::signed(INITIAL_USER_ACCOUNT),
+                vote_index,
+                BallotChoice::Score(vec![(INITIAL_USER_TWO_AADHAAR, 6)].try_into().unwrap()),
+                Default::default(),
+            ), Error::<Test>::ScoreOutOfRange,
+        );
+        assert_noop!(Ballot::cast_ballot(
+                Origin::signed(INITIAL_USER_ACCOUNT),
+                vote_index,
+                BallotChoice::Approval(vec![INITIAL_USER_TWO_AADHAAR].try_into().unwrap()),
+                Default::default(),
+            ), Error::<Test>::BallotTypeMismatch,
+        );
+
+        assert_ok!(Ballot::cast_ballot(
+			Origin::signed(INITIAL_USER_ACCOUNT),
+            vote_index,
+            BallotChoice::Score(vec![(INITIAL_USER_TWO_AADHAAR, 5), (INITIAL_USER_THREE_AADHAAR, 2)].try_into().unwrap()),
+            Default::default(),
+		));
+        assert_ok!(Ballot::cast_ballot(
+			Origin::signed(INITIAL_USER_TWO_ACCOUNT),
+            vote_index,
+            BallotChoice::Score(vec![(INITIAL_USER_THREE_AADHAAR, 4)].try_into().unwrap()),
+            Default::default(),
+		));
+
+        assert_eq!(Candidates::<Test>::get(vote_index, INITIAL_USER_TWO_AADHAAR).vote_count, 5);
+        assert_eq!(Candidates::<Test>::get(vote_index, INITIAL_USER_THREE_AADHAAR).vote_count, 6);
+	})
+}
+
+#[test]
+fn test_referendum_approved() {
+	new_test_ext().execute_with(|| {
+        let vote_index = 0;
+        System::set_block_number(1);
+
+        assert_noop!(Ballot::start_voting(
+                Origin::signed(INITIAL_USER_ACCOUNT),
+                SessionConfig { ballot_type: BallotType::Referendum, ..Default::default() },
+            ), Error::<Test>::UnsupportedBallotConfig,
+        );
+        assert_ok!(Ballot::start_referendum(
+			Origin::signed(INITIAL_USER_ACCOUNT),
+			Proposal::Text(b"Lower the voting age to 16".to_vec().try_into().unwrap()),
+			Default::default(),
+		));
+        assert_eq!(Sessions::<Test>::get(vote_index).unwrap().ballot_type, BallotType::Referendum);
+
+        assert_noop!(Ballot::add_candidates(
+                Origin::signed(INITIAL_USER_ACCOUNT),
+                vote_index,
+                vec![INITIAL_USER_TWO_ACCOUNT].try_into().unwrap(),
+            ), Error::<Test>::BallotTypeMismatch,
+        );
+        assert_noop!(Ballot::vote(
+                Origin::signed(INITIAL_USER_ACCOUNT),
+                vote_index,
+                INITIAL_USER_TWO_AADHAAR,
+                Default::default(),
+            ), Error::<Test>::BallotTypeMismatch,
+        );
+
+        assert_ok!(Ballot::cast_ballot(
+			Origin::signed(INITIAL_USER_ACCOUNT),
+            vote_index,
+            BallotChoice::Referendum(ReferendumChoice::Yes),
+            Default::default(),
+		));
+        assert_noop!(Ballot::cast_ballot(
+                Origin::signed(INITIAL_USER_ACCOUNT),
+                vote_index,
+                BallotChoice::Referendum(ReferendumChoice::No),
+                Default::default(),
+            ), Error::<Test>::VoteAlreadyCast,
+        );
+        assert_ok!(Ballot::cast_ballot(
+			Origin::signed(INITIAL_USER_TWO_ACCOUNT),
+            vote_index,
+            BallotChoice::Referendum(ReferendumChoice::Abstain),
+            Default::default(),
+		));
+
+        assert_ok!(Ballot::stop_voting(Origin::signed(INITIAL_USER_ACCOUNT), vote_index));
+
+        let tally = ReferendumTally { yes: 1, no: 0, abstain: 1 };
+        assert_eq!(ReferendumTallies::<Test>::get(vote_index), tally);
+        System::assert_last_event(crate::mock::Event::Ballot(crate::Event::ReferendumDecided {
+            vote_index,
+            approved: true,
+            tally: tally.clone(),
+        }));
+
+        // The decision is kept as the result and can no longer be reset
+        let result = Results::<Test>::get(vote_index).unwrap();
+        assert_eq!(result.referendum, Some(ReferendumOutcome { approved: true, tally }));
+        assert_eq!(result.turnout, 2);
+        assert_noop!(
+            Ballot::reset_voting(Origin::signed(INITIAL_USER_ACCOUNT), vote_index),
+            Error::<Test>::ResultAlreadyDeclared,
+        );
+	})
+}
+
+#[test]
+fn test_referendum_rejected_below_majority() {
+	new_test_ext().execute_with(|| {
+        let vote_index = 0;
+        System::set_block_number(1);
+
+        assert_ok!(Ballot::start_referendum(
+			Origin::signed(INITIAL_USER_ACCOUNT),
+			Proposal::Hash(sp_core::H256::repeat_byte(1)),
+			SessionConfig { majority: Some(Perbill::from_percent(67)), ..Default::default() },
+		));
+
+        assert_ok!(Ballot::cast_ballot(
+			Origin::signed(INITIAL_USER_ACCOUNT),
+            vote_index,
+            BallotChoice::Referendum(ReferendumChoice::Yes),
+            Default::default(),
+		));
+        assert_ok!(Ballot::cast_ballot(
+			Origin::signed(INITIAL_USER_TWO_ACCOUNT),
+            vote_index,
+            BallotChoice::Referendum(ReferendumChoice::Yes),
+            Default::default(),
+		));
+        assert_ok!(Ballot::cast_ballot(
+			Origin::signed(INITIAL_USER_THREE_ACCOUNT),
+            vote_index,
+            BallotChoice::Referendum(ReferendumChoice::No),
+            Default::default(),
+		));
+
+        assert_ok!(Ballot::stop_voting(Origin::signed(INITIAL_USER_ACCOUNT), vote_index));
+
+        System::assert_last_event(crate::mock::Event::Ballot(crate::Event::ReferendumDecided {
+            vote_index,
+            approved: false,
+            tally: ReferendumTally { yes: 2, no: 1, abstain: 0 },
+        }));
+	})
+}
+
+#[test]
+fn test_constituency_bound_session() {
+	new_test_ext().execute_with(|| {
+        let vote_index = 0;
+        let constituency_id = 7;
+
+        assert_noop!(Ballot::start_voting(
+                Origin::signed(INITIAL_USER_ACCOUNT),
+                SessionConfig { constituencies: vec![constituency_id].try_into().unwrap(), ..Default::default() },
+            ), Error::<Test>::ConstituencyNotFound,
+        );
+
+        assert_ok!(Aadhaar::add_constituency(
+			Origin::signed(INITIAL_USER_ACCOUNT),
+            constituency_id,
+            b"Varanasi".to_vec().try_into().unwrap(),
+		));
+        assert_ok!(Aadhaar::assign_constituency(
+			Origin::signed(INITIAL_USER_ACCOUNT),
+            INITIAL_USER_TWO_AADHAAR,
+            constituency_id,
+		));
+
+        assert_ok!(Ballot::start_voting(
+			Origin::signed(INITIAL_USER_ACCOUNT),
+			SessionConfig { constituencies: vec![constituency_id].try_into().unwrap(), ..Default::default() },
+		));
+        assert_ok!(Ballot::add_candidates(
+			Origin::signed(INITIAL_USER_ACCOUNT),
+            vote_index,
+            vec![INITIAL_USER_THREE_ACCOUNT].try_into().unwrap(),
+		));
+
+        assert_noop!(Ballot::vote(
+                Origin::signed(INITIAL_USER_ACCOUNT),
+                vote_index,
+                INITIAL_USER_THREE_AADHAAR,
+                Default::default(),
+            ), Error::<Test>::VoterNotInElectorate,
+        );
+        assert_ok!(Ballot::vote(Origin::signed(INITIAL_USER_TWO_ACCOUNT), vote_index, INITIAL_USER_THREE_AADHAAR, Default::default()));
+
+        assert_eq!(Candidates::<Test>::get(vote_index, INITIAL_USER_THREE_AADHAAR).vote_count, 1);
+	})
+}
+
+#[test]
+fn test_electoral_roll_proofs() {
+	new_test_ext().execute_with(|| {
+        let vote_index = 0;
+        let leaf_one = Ballot::roll_leaf(&INITIAL_USER_AADHAAR);
+        let leaf_two = Ballot::roll_leaf(&INITIAL_USER_TWO_AADHAAR);
+        let root = Ballot::roll_node(leaf_one, leaf_two);
+
+        assert_ok!(Ballot::start_voting(
+			Origin::signed(INITIAL_USER_ACCOUNT),
+			SessionConfig { electoral_roll: Some(root), ..Default::default() },
+		));
+        assert_ok!(Ballot::add_candidates(
+			Origin::signed(INITIAL_USER_ACCOUNT),
+            vote_index,
+            vec![INITIAL_USER_THREE_ACCOUNT].try_into().unwrap(),
+		));
+
+        assert_noop!(Ballot::vote(
+                Origin::signed(INITIAL_USER_ACCOUNT),
+                vote_index,
+                INITIAL_USER_THREE_AADHAAR,
+                Default::default(),
+            ), Error::<Test>::NotOnElectoralRoll,
+        );
+        // Voter three is not on the roll and cannot reuse another voter's proof
+        assert_noop!(Ballot::vote(
+                Origin::signed(INITIAL_USER_THREE_ACCOUNT),
+                vote_index,
+                INITIAL_USER_THREE_AADHAAR,
+                vec![leaf_two].try_into().unwrap(),
+            ), Error::<Test>::NotOnElectoralRoll,
+        );
+
+        assert_ok!(Ballot::vote(
+			Origin::signed(INITIAL_USER_ACCOUNT),
+            vote_index,
+            INITIAL_USER_THREE_AADHAAR,
+            vec![leaf_two].try_into().unwrap(),
+		));
+        assert_ok!(Ballot::vote(
+			Origin::signed(INITIAL_USER_TWO_ACCOUNT),
+            vote_index,
+            INITIAL_USER_THREE_AADHAAR,
+            vec![leaf_one].try_into().unwrap(),
+		));
+
+        assert_eq!(Candidates::<Test>::get(vote_index, INITIAL_USER_THREE_AADHAAR).vote_count, 2);
+	})
+}
+
+#[test]
+fn test_nomination_deposits_returned_or_slashed() {
+	new_test_ext().execute_with(|| {
+        let vote_index = 0;
+        System::set_block_number(1);
+
+        assert_ok!(Ballot::start_voting(
+			Origin::signed(INITIAL_USER_ACCOUNT),
+			SessionConfig { starts_at: Some(5), ends_at: Some(10), ..Default::default() },
+		));
+
+        assert_ok!(Ballot::nominate(Origin::signed(INITIAL_USER_ACCOUNT), vote_index));
+        assert_ok!(Ballot::nominate(Origin::signed(INITIAL_USER_TWO_ACCOUNT), vote_index));
+        assert_ok!(Ballot::nominate(Origin::signed(INITIAL_USER_THREE_ACCOUNT), vote_index));
+        assert_noop!(
+            Ballot::nominate(Origin::signed(INITIAL_USER_TWO_ACCOUNT), vote_index),
+            Error::<Test>::AlreadyNominated,
+        );
+        assert_eq!(Balances::reserved_balance(INITIAL_USER_TWO_ACCOUNT), NOMINATION_DEPOSIT);
+
+        assert_ok!(Ballot::accept_nomination(Origin::signed(INITIAL_USER_ACCOUNT), vote_index, INITIAL_USER_TWO_AADHAAR));
+        assert_ok!(Ballot::accept_nomination(Origin::signed(INITIAL_USER_ACCOUNT), vote_index, INITIAL_USER_THREE_AADHAAR));
+        assert_ok!(Ballot::reject_nomination(Origin::signed(INITIAL_USER_ACCOUNT), vote_index, INITIAL_USER_AADHAAR));
+        assert_noop!(
+            Ballot::reject_nomination(Origin::signed(INITIAL_USER_ACCOUNT), vote_index, INITIAL_USER_TWO_AADHAAR),
+            Error::<Test>::NominationAlreadyAccepted,
+        );
+        assert_eq!(Balances::reserved_balance(INITIAL_USER_ACCOUNT), 0);
+        assert!(!Candidates::<Test>::contains_key(vote_index, INITIAL_USER_AADHAAR));
+        assert!(Candidates::<Test>::contains_key(vote_index, INITIAL_USER_TWO_AADHAAR));
+
+        run_to_block(5);
+        assert_noop!(
+            Ballot::withdraw_nomination(Origin::signed(INITIAL_USER_THREE_ACCOUNT), vote_index),
+            Error::<Test>::NominationsClosed,
+        );
+
+        assert_ok!(Ballot::vote(Origin::signed(INITIAL_USER_ACCOUNT), vote_index, INITIAL_USER_TWO_AADHAAR, Default::default()));
+        assert_ok!(Ballot::vote(Origin::signed(INITIAL_USER_TWO_ACCOUNT), vote_index, INITIAL_USER_TWO_AADHAAR, Default::default()));
+        assert_ok!(Ballot::vote(Origin::signed(INITIAL_USER_THREE_ACCOUNT), vote_index, INITIAL_USER_TWO_AADHAAR, Default::default()));
+        run_to_block(10);
+
+        // Candidate three polled no votes and forfeits the deposit
+        assert_eq!(Balances::reserved_balance(INITIAL_USER_TWO_ACCOUNT), 0);
+        assert_eq!(Balances::free_balance(INITIAL_USER_TWO_ACCOUNT), INITIAL_BALANCE);
+        assert_eq!(Balances::reserved_balance(INITIAL_USER_THREE_ACCOUNT), 0);
+        assert_eq!(Balances::free_balance(INITIAL_USER_THREE_ACCOUNT), INITIAL_BALANCE - NOMINATION_DEPOSIT);
+        System::assert_has_event(crate::mock::Event::Ballot(crate::Event::DepositSlashed {
+            vote_index,
+            candidate: INITIAL_USER_THREE_AADHAAR,
+            amount: NOMINATION_DEPOSIT,
+        }));
+	})
+}
+
+#[test]
+fn test_approval_deposits_settled_on_turnout() {
+	new_test_ext().execute_with(|| {
+        let vote_index = 0;
+        System::set_block_number(1);
+
+        assert_ok!(Ballot::start_voting(
+			Origin::signed(INITIAL_USER_ACCOUNT),
+			SessionConfig { starts_at: Some(5), ends_at: Some(10), ballot_type: BallotType::Approval, ..Default::default() },
+		));
+        for account_id in [INITIAL_USER_ACCOUNT, INITIAL_USER_TWO_ACCOUNT, INITIAL_USER_THREE_ACCOUNT] {
+            assert_ok!(Ballot::nominate(Origin::signed(account_id), vote_index));
+        }
+        for aadhaar_id in [INITIAL_USER_AADHAAR, INITIAL_USER_TWO_AADHAAR, INITIAL_USER_THREE_AADHAAR] {
+            assert_ok!(Ballot::accept_nomination(Origin::signed(INITIAL_USER_ACCOUNT), vote_index, aadhaar_id));
+        }
+
+        run_to_block(5);
+        assert_ok!(Ballot::cast_ballot(
+			Origin::signed(INITIAL_USER_ACCOUNT),
+            vote_index,
+            BallotChoice::Approval(
+                vec![INITIAL_USER_AADHAAR, INITIAL_USER_TWO_AADHAAR, INITIAL_USER_THREE_AADHAAR].try_into().unwrap()
+            ),
+            Default::default(),
+		));
+        for account_id in [INITIAL_USER_TWO_ACCOUNT, INITIAL_USER_THREE_ACCOUNT] {
+            assert_ok!(Ballot::cast_ballot(
+                Origin::signed(account_id),
+                vote_index,
+                BallotChoice::Approval(vec![INITIAL_USER_AADHAAR, INITIAL_USER_TWO_AADHAAR].try_into().unwrap()),
+                Default::default(),
+            ));
+        }
+        run_to_block(10);
+
+        // Approved by a third of the voters, though only a seventh of all approvals
+        assert_eq!(Candidates::<Test>::get(vote_index, INITIAL_USER_THREE_AADHAAR).vote_count, 1);
+        assert_eq!(Balances::reserved_balance(INITIAL_USER_THREE_ACCOUNT), 0);
+        assert_eq!(Balances::free_balance(INITIAL_USER_THREE_ACCOUNT), INITIAL_BALANCE);
+        System::assert_has_event(crate::mock::Event::Ballot(crate::Event::DepositReturned {
+            vote_index,
+            candidate: INITIAL_USER_THREE_AADHAAR,
+            amount: NOMINATION_DEPOSIT,
+        }));
+	})
+}
+
+#[test]
+fn test_ranked_deposits_settled_on_best_round() {
+	new_test_ext().execute_with(|| {
+        let vote_index = 0;
+        System::set_block_number(1);
+
+        assert_ok!(Ballot::start_voting(
+			Origin::signed(INITIAL_USER_ACCOUNT),
+			SessionConfig { starts_at: Some(5), ballot_type: BallotType::RankedChoice, ..Default::default() },
+		));
+        assert_ok!(Ballot::nominate(Origin::signed(INITIAL_USER_TWO_ACCOUNT), vote_index));
+        assert_ok!(Ballot::nominate(Origin::signed(INITIAL_USER_THREE_ACCOUNT), vote_index));
+        assert_ok!(Ballot::accept_nomination(Origin::signed(INITIAL_USER_ACCOUNT), vote_index, INITIAL_USER_TWO_AADHAAR));
+        assert_ok!(Ballot::accept_nomination(Origin::signed(INITIAL_USER_ACCOUNT), vote_index, INITIAL_USER_THREE_AADHAAR));
+        assert_ok!(Ballot::add_candidates(
+			Origin::signed(INITIAL_USER_ACCOUNT),
+            vote_index,
+            vec![INITIAL_USER_ACCOUNT].try_into().unwrap(),
+		));
+
+        run_to_block(5);
+        assert_ok!(Ballot::cast_ballot(
+			Origin::signed(INITIAL_USER_ACCOUNT),
+            vote_index,
+            ranked(vec![INITIAL_USER_THREE_AADHAAR, INITIAL_USER_TWO_AADHAAR]),
+            Default::default(),
+		));
+        assert_ok!(Ballot::cast_ballot(
+			Origin::signed(INITIAL_USER_TWO_ACCOUNT),
+            vote_index,
+            ranked(vec![INITIAL_USER_TWO_AADHAAR, INITIAL_USER_THREE_AADHAAR]),
+            Default::default(),
+		));
+        assert_ok!(Ballot::cast_ballot(
+			Origin::signed(INITIAL_USER_THREE_ACCOUNT),
+            vote_index,
+            ranked(vec![INITIAL_USER_AADHAAR, INITIAL_USER_TWO_AADHAAR]),
+            Default::default(),
+		));
+        assert_ok!(Ballot::stop_voting(Origin::signed(INITIAL_USER_ACCOUNT), vote_index));
+        run_to_block(10);
+		assert_eq!(VotingState::<Test>::get(vote_index), VoteState::Ended);
+
+        // Candidate three is eliminated first with a third of the ballots and keeps the deposit
+		assert_eq!(Eliminated::<Test>::get(vote_index, INITIAL_USER_THREE_AADHAAR), Some(1));
+        assert_eq!(Balances::reserved_balance(INITIAL_USER_THREE_ACCOUNT), 0);
+        assert_eq!(Balances::free_balance(INITIAL_USER_THREE_ACCOUNT), INITIAL_BALANCE);
+        System::assert_has_event(crate::mock::Event::Ballot(crate::Event::DepositReturned {
+            vote_index,
+            candidate: INITIAL_USER_THREE_AADHAAR,
+            amount: NOMINATION_DEPOSIT,
+        }));
+	})
+}
+
+#[test]
+fn test_withdraw_nomination_before_polling() {
+	new_test_ext().execute_with(|| {
+        let vote_index = 0;
+
+        assert_ok!(Ballot::start_voting(
+			Origin::signed(INITIAL_USER_ACCOUNT),
+			SessionConfig { starts_at: Some(5), ..Default::default() },
+		));
+        assert_noop!(Ballot::add_candidates(
+                Origin::signed(INITIAL_USER_ACCOUNT),
+                vote_index,
+                vec![INITIAL_USER_TWO_ACCOUNT, 42].try_into().unwrap(),
+            ), Error::<Test>::CandidateNotRegistered,
+        );
+
+        assert_ok!(Ballot::nominate(Origin::signed(INITIAL_USER_TWO_ACCOUNT), vote_index));
+        assert_ok!(Ballot::accept_nomination(Origin::signed(INITIAL_USER_ACCOUNT), vote_index, INITIAL_USER_TWO_AADHAAR));
+        assert_ok!(Ballot::withdraw_nomination(Origin::signed(INITIAL_USER_TWO_ACCOUNT), vote_index));
+
+        assert_eq!(Balances::reserved_balance(INITIAL_USER_TWO_ACCOUNT), 0);
+        assert!(!Candidates::<Test>::contains_key(vote_index, INITIAL_USER_TWO_AADHAAR));
+        assert_noop!(
+            Ballot::withdraw_nomination(Origin::signed(INITIAL_USER_TWO_ACCOUNT), vote_index),
+            Error::<Test>::NominationNotFound,
+        );
+	})
+}
+
+#[test]
+fn test_candidates_per_session_bounded() {
+	new_test_ext().execute_with(|| {
+        let vote_index = 0;
+        let extra_accounts: Vec<AccounId> = (10..14).collect();
+        for account in &extra_accounts {
+            assert_ok!(pallet_aadhaar::Pallet::<Test>::do_register_aadhaar(account, [*account as u8; 16]));
+        }
+
+        assert_ok!(Ballot::start_voting(
+			Origin::signed(INITIAL_USER_ACCOUNT),
+			SessionConfig { starts_at: Some(5), ..Default::default() },
+		));
+        // Listing a candidate twice takes a single slot
+        assert_ok!(Ballot::add_candidates(
+			Origin::signed(INITIAL_USER_ACCOUNT),
+            vote_index,
+            vec![10, 11, 11].try_into().unwrap(),
+		));
+        assert_ok!(Ballot::add_candidates(
+			Origin::signed(INITIAL_USER_ACCOUNT),
+            vote_index,
+            vec![11, 12, 13].try_into().unwrap(),
+		));
+        assert_ok!(Ballot::nominate(Origin::signed(INITIAL_USER_TWO_ACCOUNT), vote_index));
+        assert_eq!(CandidateCount::<Test>::get(vote_index), 5);
+
+        assert_noop!(Ballot::add_candidates(
+                Origin::signed(INITIAL_USER_ACCOUNT),
+                vote_index,
+                vec![INITIAL_USER_THREE_ACCOUNT].try_into().unwrap(),
+            ), Error::<Test>::TooManyCandidates,
+        );
+        assert_noop!(
+            Ballot::nominate(Origin::signed(INITIAL_USER_THREE_ACCOUNT), vote_index),
+            Error::<Test>::TooManyCandidates,
+        );
+
+        // A withdrawn nomination frees its slot
+        assert_ok!(Ballot::withdraw_nomination(Origin::signed(INITIAL_USER_TWO_ACCOUNT), vote_index));
+        assert_ok!(Ballot::nominate(Origin::signed(INITIAL_USER_THREE_ACCOUNT), vote_index));
+
+        // Resetting clears every candidate of the full session
+        assert_ok!(Ballot::reset_voting(Origin::signed(INITIAL_USER_ACCOUNT), vote_index));
+        assert_eq!(Candidates::<Test>::iter_prefix(vote_index).count(), 0);
+        assert_eq!(CandidateCount::<Test>::get(vote_index), 0);
+	})
+}
+
+#[test]
+fn test_candidate_profiles_locked_once_polling_opens() {
+	new_test_ext().execute_with(|| {
+        let vote_index = 0;
+        let party_id = 1;
+        let profile = CandidateProfile::<Test> {
+            name: b"Candidate Two".to_vec().try_into().unwrap(),
+            party: Some(party_id),
+            symbol: 9,
+            manifesto: Some(b"bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi".to_vec().try_into().unwrap()),
+        };
+
+        assert_ok!(Ballot::start_voting(
+			Origin::signed(INITIAL_USER_ACCOUNT),
+			SessionConfig { starts_at: Some(5), ..Default::default() },
+		));
+        assert_ok!(Ballot::add_candidates(
+			Origin::signed(INITIAL_USER_ACCOUNT),
+            vote_index,
+            vec![INITIAL_USER_TWO_ACCOUNT].try_into().unwrap(),
+		));
+
+        assert_noop!(Ballot::set_candidate_profile(
+                Origin::signed(INITIAL_USER_TWO_ACCOUNT),
+                vote_index,
+                INITIAL_USER_TWO_AADHAAR,
+                profile.clone(),
+            ), Error::<Test>::PartyNotFound,
+        );
+
+        assert_ok!(Ballot::register_party(
+			Origin::signed(INITIAL_USER_ACCOUNT),
+            party_id,
+            Party { name: b"Progressive Party".to_vec().try_into().unwrap(), symbol: 9 },
+		));
+        assert_noop!(Ballot::register_party(
+                Origin::signed(INITIAL_USER_ACCOUNT),
+                party_id,
+                Party { name: b"Other Party".to_vec().try_into().unwrap(), symbol: 3 },
+            ), Error::<Test>::PartyAlreadyExists,
+        );
+
+        assert_ok!(Ballot::set_candidate_profile(
+			Origin::signed(INITIAL_USER_TWO_ACCOUNT),
+            vote_index,
+            INITIAL_USER_TWO_AADHAAR,
+            profile.clone(),
+		));
+        assert_eq!(CandidateProfiles::<Test>::get(vote_index, INITIAL_USER_TWO_AADHAAR), Some(profile.clone()));
+
+        run_to_block(5);
+        assert_noop!(Ballot::set_candidate_profile(
+                Origin::signed(INITIAL_USER_TWO_ACCOUNT),
+                vote_index,
+                INITIAL_USER_TWO_AADHAAR,
+                profile,
+            ), Error::<Test>::ProfileLocked,
+        );
+        assert_noop!(Ballot::add_candidates(
+                Origin::signed(INITIAL_USER_ACCOUNT),
+                vote_index,
+                vec![INITIAL_USER_THREE_ACCOUNT].try_into().unwrap(),
+            ), Error::<Test>::CandidatesLocked,
+        );
+	})
+}
+
+#[test]
+fn test_candidate_profiles_of_immediate_sessions_locked_on_first_ballot() {
+	new_test_ext().execute_with(|| {
+        let vote_index = 0;
+        let profile = CandidateProfile::<Test> {
+            name: b"Candidate Two".to_vec().try_into().unwrap(),
+            party: None,
+            symbol: 9,
+            manifesto: None,
+        };
+
+        assert_ok!(Ballot::start_voting(Origin::signed(INITIAL_USER_ACCOUNT), Default::default()));
+        assert_eq!(VotingState::<Test>::get(vote_index), VoteState::Voting);
+        assert_ok!(Ballot::add_candidates(
+			Origin::signed(INITIAL_USER_ACCOUNT),
+            vote_index,
+            vec![INITIAL_USER_TWO_ACCOUNT].try_into().unwrap(),
+		));
+
+        // Sessions opened at once take candidates and profiles until the first ballot
+        assert_ok!(Ballot::set_candidate_profile(
+			Origin::signed(INITIAL_USER_TWO_ACCOUNT),
+            vote_index,
+            INITIAL_USER_TWO_AADHAAR,
+            profile.clone(),
+		));
+        assert_eq!(CandidateProfiles::<Test>::get(vote_index, INITIAL_USER_TWO_AADHAAR), Some(profile.clone()));
+
+        assert_ok!(Ballot::vote(Origin::signed(INITIAL_USER_ACCOUNT), vote_index, INITIAL_USER_TWO_AADHAAR, Default::default()));
+        assert_noop!(Ballot::set_candidate_profile(
+                Origin::signed(INITIAL_USER_TWO_ACCOUNT),
+                vote_index,
+                INITIAL_USER_TWO_AADHAAR,
+                profile,
+            ), Error::<Test>::ProfileLocked,
+        );
+        assert_noop!(Ballot::add_candidates(
+                Origin::signed(INITIAL_USER_ACCOUNT),
+                vote_index,
+                vec![INITIAL_USER_THREE_ACCOUNT].try_into().unwrap(),
+            ), Error::<Test>::CandidatesLocked,
+        );
+	})
+}
+
+#[test]
+fn test_nota_votes_and_rules() {
+	new_test_ext().execute_with(|| {
+        let vote_index = 0;
+        System::set_block_number(1);
+
+        assert_ok!(Ballot::start_voting(
+			Origin::signed(INITIAL_USER_ACCOUNT),
+			SessionConfig { nota_rule: NotaRule::Repoll, ..Default::default() },
+		));
+        assert_ok!(Ballot::add_candidates(
+			Origin::signed(INITIAL_USER_ACCOUNT),
+            vote_index,
+            vec![INITIAL_USER_TWO_ACCOUNT, INITIAL_USER_THREE_ACCOUNT].try_into().unwrap(),
+		));
+
+        assert_ok!(Ballot::vote(Origin::signed(INITIAL_USER_ACCOUNT), vote_index, NOTA, Default::default()));
+        assert_ok!(Ballot::vote(Origin::signed(INITIAL_USER_TWO_ACCOUNT), vote_index, NOTA, Default::default()));
+        assert_ok!(Ballot::vote(Origin::signed(INITIAL_USER_THREE_ACCOUNT), vote_index, INITIAL_USER_THREE_AADHAAR, Default::default()));
+        assert_eq!(NotaTallies::<Test>::get(vote_index), 2);
+        assert!(!Candidates::<Test>::contains_key(vote_index, NOTA));
+
+        assert_ok!(Ballot::stop_voting(Origin::signed(INITIAL_USER_ACCOUNT), vote_index));
+
+        // The repoll starts without candidates for the commission to fill
+        let repoll_index = 1;
+        System::assert_has_event(crate::mock::Event::Ballot(crate::Event::NotaWon { vote_index, votes: 2 }));
+        System::assert_last_event(crate::mock::Event::Ballot(crate::Event::RepollStarted { vote_index, repoll_index }));
+        assert_eq!(ParentSession::<Test>::get(repoll_index), Some(vote_index));
+        assert_eq!(Candidates::<Test>::iter_prefix(repoll_index).count(), 0);
+
+        // It takes candidates and nominations until it opens after the repoll delay
+		assert_eq!(VotingState::<Test>::get(repoll_index), VoteState::Idle);
+        assert_ok!(Ballot::add_candidates(
+			Origin::signed(INITIAL_USER_ACCOUNT),
+            repoll_index,
+            vec![INITIAL_USER_ACCOUNT].try_into().unwrap(),
+		));
+        assert_ok!(Ballot::nominate(Origin::signed(INITIAL_USER_TWO_ACCOUNT), repoll_index));
+        run_to_block(6);
+		assert_eq!(VotingState::<Test>::get(repoll_index), VoteState::Voting);
+        assert_ok!(Ballot::vote(Origin::signed(INITIAL_USER_ACCOUNT), repoll_index, INITIAL_USER_AADHAAR, Default::default()));
+	})
+}
+
+#[test]
+fn test_nota_on_multi_choice_ballots() {
+	new_test_ext().execute_with(|| {
+        let vote_index = 0;
+        System::set_block_number(1);
+
+        assert_ok!(Ballot::start_voting(
+			Origin::signed(INITIAL_USER_ACCOUNT),
+			SessionConfig { ballot_type: BallotType::Approval, nota_rule: NotaRule::NoWinner, ..Default::default() },
+		));
+        assert_ok!(Ballot::add_candidates(
+			Origin::signed(INITIAL_USER_ACCOUNT),
+            vote_index,
+            vec![INITIAL_USER_TWO_ACCOUNT, INITIAL_USER_THREE_ACCOUNT].try_into().unwrap(),
+		));
+
+        // NOTA can only be approved on its own
+        assert_noop!(Ballot::cast_ballot(
+                Origin::signed(INITIAL_USER_ACCOUNT),
+                vote_index,
+                BallotChoice::Approval(vec![INITIAL_USER_TWO_AADHAAR, NOTA].try_into().unwrap()),
+                Default::default(),
+            ), Error::<Test>::ReservedCandidateId,
+        );
+        assert_ok!(Ballot::cast_ballot(
+			Origin::signed(INITIAL_USER_ACCOUNT),
+            vote_index,
+            BallotChoice::Approval(vec![NOTA].try_into().unwrap()),
+            Default::default(),
+		));
+        assert_ok!(Ballot::cast_ballot(
+			Origin::signed(INITIAL_USER_TWO_ACCOUNT),
+            vote_index,
+            BallotChoice::Approval(vec![NOTA].try_into().unwrap()),
+            Default::default(),
+		));
+        assert_ok!(Ballot::cast_ballot(
+			Origin::signed(INITIAL_USER_THREE_ACCOUNT),
+            vote_index,
+            BallotChoice::Approval(vec![INITIAL_USER_THREE_AADHAAR].try_into().unwrap()),
+            Default::default(),
+		));
+        assert_eq!(NotaTallies::<Test>::get(vote_index), 2);
+
+        assert_ok!(Ballot::stop_voting(Origin::signed(INITIAL_USER_ACCOUNT), vote_index));
+        System::assert_has_event(crate::mock::Event::Ballot(crate::Event::NotaWon { vote_index, votes: 2 }));
+        assert_eq!(Ballot::results(vote_index).unwrap().winners, vec![]);
+
+        // Ranked and score ballots take NOTA the same way
+        let ranked_index = 1;
+        assert_ok!(Ballot::start_voting(
+			Origin::signed(INITIAL_USER_ACCOUNT),
+			SessionConfig { ballot_type: BallotType::RankedChoice, ..Default::default() },
+		));
+        assert_ok!(Ballot::add_candidates(
+			Origin::signed(INITIAL_USER_ACCOUNT),
+            ranked_index,
+            vec![INITIAL_USER_TWO_ACCOUNT].try_into().unwrap(),
+		));
+        assert_ok!(Ballot::cast_ballot(Origin::signed(INITIAL_USER_ACCOUNT), ranked_index, ranked(vec![NOTA]), Default::default()));
+        assert_eq!(NotaTallies::<Test>::get(ranked_index), 1);
+        assert_eq!(RankedBallotCount::<Test>::get(ranked_index), 0);
+
+        let score_index = 2;
+        assert_ok!(Ballot::start_voting(
+			Origin::signed(INITIAL_USER_ACCOUNT),
+			SessionConfig { ballot_type: BallotType::Score { max_score: 5 }, ..Default::default() },
+		));
+        assert_ok!(Ballot::add_candidates(
+			Origin::signed(INITIAL_USER_ACCOUNT),
+            score_index,
+            vec![INITIAL_USER_TWO_ACCOUNT].try_into().unwrap(),
+		));
+        assert_ok!(Ballot::cast_ballot(
+			Origin::signed(INITIAL_USER_ACCOUNT),
+            score_index,
+            BallotChoice::Score(vec![(NOTA, 5)].try_into().unwrap()),
+            Default::default(),
+		));
+        assert_eq!(NotaTallies::<Test>::get(score_index), 1);
+        assert_eq!(Votes::<Test>::get(score_index, INITIAL_USER_AADHAAR), true);
+	})
+}
+
+#[test]
+fn test_nota_disregarded_by_default() {
+	new_test_ext().execute_with(|| {
+        let vote_index = 0;
+        System::set_block_number(1);
+
+        assert_ok!(Ballot::start_voting(Origin::signed(INITIAL_USER_ACCOUNT), Default::default()));
+        assert_ok!(Ballot::add_candidates(
+			Origin::signed(INITIAL_USER_ACCOUNT),
+            vote_index,
+            vec![INITIAL_USER_TWO_ACCOUNT].try_into().unwrap(),
+		));
+
+        assert_ok!(Ballot::vote(Origin::signed(INITIAL_USER_ACCOUNT), vote_index, NOTA, Default::default()));
+        assert_ok!(Ballot::vote(Origin::signed(INITIAL_USER_TWO_ACCOUNT), vote_index, NOTA, Default::default()));
+        assert_ok!(Ballot::stop_voting(Origin::signed(INITIAL_USER_ACCOUNT), vote_index));
+
+        System::assert_last_event(crate::mock::Event::Ballot(crate::Event::ElectionDecided {
+            vote_index,
+            first_round: vote_index,
+            winner: Some(INITIAL_USER_TWO_AADHAAR),
+        }));
+	})
+}
+
+#[test]
+fn test_result_declared_and_immutable() {
+	new_test_ext().execute_with(|| {
+        let vote_index = 0;
+        System::set_block_number(1);
+
+        assert_ok!(Ballot::start_voting(Origin::signed(INITIAL_USER_ACCOUNT), Default::default()));
+        assert_ok!(Ballot::add_candidates(
+			Origin::signed(INITIAL_USER_ACCOUNT),
+            vote_index,
+            vec![INITIAL_USER_ACCOUNT, INITIAL_USER_TWO_ACCOUNT, INITIAL_USER_THREE_ACCOUNT].try_into().unwrap(),
+		));
+
+        assert_ok!(Ballot::vote(Origin::signed(INITIAL_USER_ACCOUNT), vote_index, INITIAL_USER_THREE_AADHAAR, Default::default()));
+        assert_ok!(Ballot::vote(Origin::signed(INITIAL_USER_TWO_ACCOUNT), vote_index, INITIAL_USER_TWO_AADHAAR, Default::default()));
+        assert_ok!(Ballot::vote(Origin::signed(INITIAL_USER_THREE_ACCOUNT), vote_index, NOTA, Default::default()));
+        assert_eq!(Results::<Test>::get(vote_index), None);
+
+        assert_ok!(Ballot::stop_voting(Origin::signed(INITIAL_USER_ACCOUNT), vote_index));
+
+        let result = Results::<Test>::get(vote_index).unwrap();
+        assert_eq!(result.standings, vec![
+            Candidate { aadhaar_id: INITIAL_USER_TWO_AADHAAR, vote_count: 1 },
+            Candidate { aadhaar_id: INITIAL_USER_THREE_AADHAAR, vote_count: 1 },
+            Candidate { aadhaar_id: INITIAL_USER_AADHAAR, vote_count: 0 },
+        ]);
+        assert_eq!(result.nota, 1);
+        assert_eq!(result.winners, vec![INITIAL_USER_TWO_AADHAAR, INITIAL_USER_THREE_AADHAAR]);
+        assert_eq!(result.turnout, 3);
+        assert!(result.tied);
+        System::assert_has_event(crate::mock::Event::Ballot(crate::Event::ResultDeclared {
+            vote_index,
+            winners: vec![INITIAL_USER_TWO_AADHAAR, INITIAL_USER_THREE_AADHAAR],
+            tied: true,
+        }));
+
+        assert_noop!(
+            Ballot::reset_voting(Origin::signed(INITIAL_USER_ACCOUNT), vote_index),
+            Error::<Test>::ResultAlreadyDeclared,
+        );
+	})
+}
+
+fn start_tied_session(tie_break: TieBreak) -> VoteIndex {
+    let vote_index = Ballot::current_vote_index();
+
+    assert_ok!(Ballot::start_voting(
+        Origin::signed(INITIAL_USER_ACCOUNT),
+        SessionConfig { tie_break, ..Default::default() },
+    ));
+    assert_ok!(Ballot::add_candidates(
+        Origin::signed(INITIAL_USER_ACCOUNT),
+        vote_index,
+        vec![INITIAL_USER_TWO_ACCOUNT, INITIAL_USER_THREE_ACCOUNT].try_into().unwrap(),
+    ));
+    assert_ok!(Ballot::vote(Origin::signed(INITIAL_USER_ACCOUNT), vote_index, INITIAL_USER_TWO_AADHAAR, Default::default()));
+    assert_ok!(Ballot::vote(Origin::signed(INITIAL_USER_TWO_ACCOUNT), vote_index, INITIAL_USER_THREE_AADHAAR, Default::default()));
+    assert_ok!(Ballot::stop_voting(Origin::signed(INITIAL_USER_ACCOUNT), vote_index));
+
+    vote_index
+}
+
+#[test]
+fn test_commission_breaks_tie() {
+	new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let vote_index = start_tied_session(TieBreak::Commission);
+
+        System::assert_last_event(crate::mock::Event::Ballot(crate::Event::TieBreakPending {
+            vote_index,
+            method: TieBreak::Commission,
+            candidates: vec![INITIAL_USER_TWO_AADHAAR, INITIAL_USER_THREE_AADHAAR],
+        }));
+        assert_noop!(
+            Ballot::break_tie(Origin::signed(INITIAL_USER_ACCOUNT), vote_index, INITIAL_USER_AADHAAR),
+            Error::<Test>::NotATiedCandidate,
+        );
+        assert_noop!(
+            Ballot::commit_tie_seed(Origin::signed(INITIAL_USER_ACCOUNT), vote_index, Default::default()),
+            Error::<Test>::TieBreakMethodMismatch,
+        );
+
+        assert_ok!(Ballot::break_tie(Origin::signed(INITIAL_USER_ACCOUNT), vote_index, INITIAL_USER_THREE_AADHAAR));
+
+        assert_eq!(TieBreakRecords::<Test>::get(vote_index), Some(TieBreakRecord {
+            method: TieBreak::Commission,
+            seed: None,
+            winner: Some(INITIAL_USER_THREE_AADHAAR),
+        }));
+        let result = Results::<Test>::get(vote_index).unwrap();
+        assert_eq!(result.winners, vec![INITIAL_USER_THREE_AADHAAR]);
+        assert!(!result.tied);
+        System::assert_last_event(crate::mock::Event::Ballot(crate::Event::ElectionDecided {
+            vote_index,
+            first_round: vote_index,
+            winner: Some(INITIAL_USER_THREE_AADHAAR),
+        }));
+        assert_noop!(
+            Ballot::break_tie(Origin::signed(INITIAL_USER_ACCOUNT), vote_index, INITIAL_USER_TWO_AADHAAR),
+            Error::<Test>::NoTieToBreak,
+        );
+	})
+}
+
+#[test]
+fn test_lot_breaks_tie_from_revealed_seed() {
+	new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let vote_index = start_tied_session(TieBreak::Lot);
+        let secret = [7u8; 32];
+        let commitment = <Test as frame_system::Config>::Hashing::hash_of(&secret);
+
+        assert_ok!(Ballot::commit_tie_seed(Origin::signed(INITIAL_USER_ACCOUNT), vote_index, commitment));
+        assert_noop!(
+            Ballot::reveal_tie_seed(Origin::signed(INITIAL_USER_ACCOUNT), vote_index, secret),
+            Error::<Test>::TieSeedRevealNotActive,
+        );
+
+        run_to_block(3);
+        assert_noop!(
+            Ballot::commit_tie_seed(Origin::signed(INITIAL_USER_TWO_ACCOUNT), vote_index, commitment),
+            Error::<Test>::TieSeedCommitClosed,
+        );
+        assert_noop!(
+            Ballot::reveal_tie_seed(Origin::signed(INITIAL_USER_ACCOUNT), vote_index, [8u8; 32]),
+            Error::<Test>::CommitmentMismatch,
+        );
+        assert_ok!(Ballot::reveal_tie_seed(Origin::signed(INITIAL_USER_ACCOUNT), vote_index, secret));
+
+        run_to_block(5);
+        let record = TieBreakRecords::<Test>::get(vote_index).unwrap();
+        assert_eq!(record.method, TieBreak::Lot);
+        assert!(record.seed.is_some());
+        let winner = record.winner.unwrap();
+        assert!(winner == INITIAL_USER_TWO_AADHAAR || winner == INITIAL_USER_THREE_AADHAAR);
+        assert_eq!(PendingTieBreaks::<Test>::get(vote_index), None);
+        let result = Results::<Test>::get(vote_index).unwrap();
+        assert_eq!(result.winners, vec![winner]);
+        assert!(!result.tied);
+        System::assert_last_event(crate::mock::Event::Ballot(crate::Event::ElectionDecided {
+            vote_index,
+            first_round: vote_index,
+            winner: Some(winner),
+        }));
+	})
+}
+
+#[test]
+fn test_vote_recast_while_voting() {
+	new_test_ext().execute_with(|| {
+        let vote_index = 0;
+        System::set_block_number(1);
+
+        assert_noop!(Ballot::start_voting(
+                Origin::signed(INITIAL_USER_ACCOUNT),
+                SessionConfig { allow_recast: true, secret: true, ..Default::default() },
+            ), Error::<Test>::UnsupportedBallotConfig,
+        );
+        assert_ok!(Ballot::start_voting(
+			Origin::signed(INITIAL_USER_ACCOUNT),
+			SessionConfig { allow_recast: true, ..Default::default() },
+		));
+        assert_ok!(Ballot::add_candidates(
+			Origin::signed(INITIAL_USER_ACCOUNT),
+            vote_index,
+            vec![INITIAL_USER_TWO_ACCOUNT, INITIAL_USER_THREE_ACCOUNT].try_into().unwrap(),
+		));
+
+        assert_ok!(Ballot::vote(Origin::signed(INITIAL_USER_ACCOUNT), vote_index, INITIAL_USER_TWO_AADHAAR, Default::default()));
+        assert_ok!(Ballot::vote(Origin::signed(INITIAL_USER_ACCOUNT), vote_index, NOTA, Default::default()));
+        assert_ok!(Ballot::vote(Origin::signed(INITIAL_USER_ACCOUNT), vote_index, INITIAL_USER_THREE_AADHAAR, Default::default()));
+        System::assert_last_event(crate::mock::Event::Ballot(crate::Event::VoteChanged {
+            vote_index,
+            from: NOTA,
+            to: INITIAL_USER_THREE_AADHAAR,
+        }));
+
+        assert_eq!(Candidates::<Test>::get(vote_index, INITIAL_USER_TWO_AADHAAR).vote_count, 0);
+        assert_eq!(Candidates::<Test>::get(vote_index, INITIAL_USER_THREE_AADHAAR).vote_count, 1);
+        assert_eq!(NotaTallies::<Test>::get(vote_index), 0);
+        assert_eq!(VotedFor::<Test>::get(vote_index, INITIAL_USER_AADHAAR), Some(INITIAL_USER_THREE_AADHAAR));
+        assert_eq!(Turnout::<Test>::get(vote_index), 1);
+
+        assert_ok!(Ballot::stop_voting(Origin::signed(INITIAL_USER_ACCOUNT), vote_index));
+        assert_noop!(
+            Ballot::vote(Origin::signed(INITIAL_USER_ACCOUNT), vote_index, INITIAL_USER_TWO_AADHAAR, Default::default()),
+            Error::<Test>::VotingNotActive,
+        );
+	})
+}
+
+#[test]
+fn test_delegated_voting() {
+	new_test_ext().execute_with(|| {
+        let vote_index = 0;
+        System::set_block_number(1);
+
+        assert_ok!(Ballot::start_voting(Origin::signed(INITIAL_USER_ACCOUNT), Default::default()));
+        assert_ok!(Ballot::add_candidates(
+			Origin::signed(INITIAL_USER_ACCOUNT),
+            vote_index,
+            vec![INITIAL_USER_TWO_ACCOUNT].try_into().unwrap(),
+		));
+
+        assert_noop!(
+            Ballot::vote_for(Origin::signed(INITIAL_USER_THREE_ACCOUNT), INITIAL_USER_AADHAAR, vote_index, INITIAL_USER_TWO_AADHAAR, Default::default()),
+            Error::<Test>::DelegationNotFound,
+        );
+        assert_noop!(
+            Ballot::delegate_vote(Origin::signed(INITIAL_USER_ACCOUNT), INITIAL_USER_THREE_ACCOUNT, DelegationScope::Session(vote_index), 1),
+            Error::<Test>::InvalidDelegation,
+        );
+        assert_ok!(Ballot::delegate_vote(
+			Origin::signed(INITIAL_USER_ACCOUNT),
+            INITIAL_USER_THREE_ACCOUNT,
+            DelegationScope::Session(vote_index),
+            10,
+		));
+        // Delegates are capped at one voter in the mock runtime
+        assert_noop!(
+            Ballot::delegate_vote(Origin::signed(INITIAL_USER_TWO_ACCOUNT), INITIAL_USER_THREE_ACCOUNT, DelegationScope::AllSessions, 10),
+            Error::<Test>::TooManyDelegators,
+        );
+        assert_noop!(
+            Ballot::vote_for(Origin::signed(INITIAL_USER_TWO_ACCOUNT), INITIAL_USER_AADHAAR, vote_index, INITIAL_USER_TWO_AADHAAR, Default::default()),
+            Error::<Test>::NotDelegate,
+        );
+        assert_noop!(
+            Ballot::vote_for(Origin::signed(INITIAL_USER_THREE_ACCOUNT), INITIAL_USER_AADHAAR, vote_index + 1, INITIAL_USER_TWO_AADHAAR, Default::default()),
+            Error::<Test>::NotDelegate,
+        );
+
+        assert_ok!(Ballot::vote_for(
+			Origin::signed(INITIAL_USER_THREE_ACCOUNT),
+            INITIAL_USER_AADHAAR,
+            vote_index,
+            INITIAL_USER_TWO_AADHAAR,
+            Default::default(),
+		));
+        assert_eq!(Votes::<Test>::get(vote_index, INITIAL_USER_AADHAAR), true);
+        assert_eq!(Votes::<Test>::get(vote_index, INITIAL_USER_THREE_AADHAAR), false);
+        assert_noop!(
+            Ballot::vote(Origin::signed(INITIAL_USER_ACCOUNT), vote_index, INITIAL_USER_TWO_AADHAAR, Default::default()),
+            Error::<Test>::VoteAlreadyCast,
+        );
+
+        assert_ok!(Ballot::revoke_delegation(Origin::signed(INITIAL_USER_ACCOUNT)));
+        assert_eq!(Delegations::<Test>::get(INITIAL_USER_AADHAAR), None);
+        assert!(DelegatorsOf::<Test>::get(INITIAL_USER_THREE_ACCOUNT).is_empty());
+        assert_ok!(Ballot::delegate_vote(
+			Origin::signed(INITIAL_USER_TWO_ACCOUNT),
+            INITIAL_USER_THREE_ACCOUNT,
+            DelegationScope::AllSessions,
+            10,
+		));
+	})
+}
+
+#[test]
+fn test_revoked_delegator_cannot_vote_through_delegate() {
+	new_test_ext().execute_with(|| {
+        let vote_index = 0;
+        System::set_block_number(1);
+
+        assert_ok!(Ballot::start_voting(Origin::signed(INITIAL_USER_ACCOUNT), Default::default()));
+        assert_ok!(Ballot::add_candidates(
+			Origin::signed(INITIAL_USER_ACCOUNT),
+            vote_index,
+            vec![INITIAL_USER_TWO_ACCOUNT].try_into().unwrap(),
+		));
+        assert_ok!(Ballot::delegate_vote(
+			Origin::signed(INITIAL_USER_ACCOUNT),
+            INITIAL_USER_THREE_ACCOUNT,
+            DelegationScope::AllSessions,
+            10,
+		));
+
+        assert_ok!(Aadhaar::revoke_aadhaar(Origin::signed(INITIAL_USER_TWO_ACCOUNT), INITIAL_USER_AADHAAR));
+        assert_noop!(
+            Ballot::vote_for(Origin::signed(INITIAL_USER_THREE_ACCOUNT), INITIAL_USER_AADHAAR, vote_index, INITIAL_USER_TWO_AADHAAR, Default::default()),
+            Error::<Test>::VoterNotRegistered,
+        );
+        assert_eq!(Votes::<Test>::get(vote_index, INITIAL_USER_AADHAAR), false);
+	})
+}
+
+#[test]
+fn test_votes_weighted_by_locked_balance() {
+	new_test_ext().execute_with(|| {
+        let vote_index = 0;
+
+        assert_ok!(Ballot::start_voting(
+			Origin::signed(INITIAL_USER_ACCOUNT),
+			SessionConfig { weight_source: WeightSource::LockedBalance, ..Default::default() },
+		));
+        assert_ok!(Ballot::add_candidates(
+			Origin::signed(INITIAL_USER_ACCOUNT),
+            vote_index,
+            vec![INITIAL_USER_TWO_ACCOUNT, INITIAL_USER_THREE_ACCOUNT].try_into().unwrap(),
+		));
+
+        assert_noop!(
+            Ballot::vote(Origin::signed(INITIAL_USER_ACCOUNT), vote_index, INITIAL_USER_TWO_AADHAAR, Default::default()),
+            Error::<Test>::NoVoteWeight,
+        );
+        assert_noop!(
+            Ballot::lock_vote_weight(Origin::signed(INITIAL_USER_ACCOUNT), vote_index, INITIAL_BALANCE + 1),
+            Error::<Test>::InvalidLockAmount,
+        );
+        assert_ok!(Ballot::lock_vote_weight(Origin::signed(INITIAL_USER_ACCOUNT), vote_index, 300));
+        assert_ok!(Ballot::lock_vote_weight(Origin::signed(INITIAL_USER_TWO_ACCOUNT), vote_index, 50));
+        assert_eq!(Balances::usable_balance(INITIAL_USER_ACCOUNT), INITIAL_BALANCE - 300);
+
+        assert_ok!(Ballot::vote(Origin::signed(INITIAL_USER_ACCOUNT), vote_index, INITIAL_USER_TWO_AADHAAR, Default::default()));
+        assert_ok!(Ballot::vote(Origin::signed(INITIAL_USER_TWO_ACCOUNT), vote_index, INITIAL_USER_THREE_AADHAAR, Default::default()));
+        assert_eq!(Candidates::<Test>::get(vote_index, INITIAL_USER_TWO_AADHAAR).vote_count, 300);
+        assert_eq!(Candidates::<Test>::get(vote_index, INITIAL_USER_THREE_AADHAAR).vote_count, 50);
+
+        assert_noop!(
+            Ballot::unlock_vote_weight(Origin::signed(INITIAL_USER_ACCOUNT), vote_index),
+            Error::<Test>::SessionNotEnded,
+        );
+        assert_ok!(Ballot::stop_voting(Origin::signed(INITIAL_USER_ACCOUNT), vote_index));
+        assert_ok!(Ballot::unlock_vote_weight(Origin::signed(INITIAL_USER_ACCOUNT), vote_index));
+        assert_eq!(Balances::usable_balance(INITIAL_USER_ACCOUNT), INITIAL_BALANCE);
+
+        // Locks can still be released once the session is archived
+        run_to_block(51);
+        assert_eq!(VotingState::<Test>::get(vote_index), VoteState::Archived);
+        assert_ok!(Ballot::unlock_vote_weight(Origin::signed(INITIAL_USER_TWO_ACCOUNT), vote_index));
+        assert_eq!(Balances::usable_balance(INITIAL_USER_TWO_ACCOUNT), INITIAL_BALANCE);
+	})
+}
+
+#[test]
+fn test_votes_weighted_by_commission_table() {
+	new_test_ext().execute_with(|| {
+        let vote_index = 0;
+
+        assert_ok!(Ballot::start_voting(
+			Origin::signed(INITIAL_USER_ACCOUNT),
+			SessionConfig { starts_at: Some(5), weight_source: WeightSource::Commission, ..Default::default() },
+		));
+        assert_ok!(Ballot::add_candidates(
+			Origin::signed(INITIAL_USER_ACCOUNT),
+            vote_index,
+            vec![INITIAL_USER_TWO_ACCOUNT].try_into().unwrap(),
+		));
+        assert_ok!(Ballot::set_vote_weights(
+			Origin::signed(INITIAL_USER_ACCOUNT),
+            vote_index,
+            vec![(INITIAL_USER_AADHAAR, 10)],
+		));
+
+        run_to_block(5);
+        assert_noop!(
+            Ballot::set_vote_weights(Origin::signed(INITIAL_USER_ACCOUNT), vote_index, vec![(INITIAL_USER_TWO_AADHAAR, 10)]),
+            Error::<Test>::VoteWeightsLocked,
+        );
+
+        assert_ok!(Ballot::vote(Origin::signed(INITIAL_USER_ACCOUNT), vote_index, INITIAL_USER_TWO_AADHAAR, Default::default()));
+        assert_noop!(
+            Ballot::vote(Origin::signed(INITIAL_USER_TWO_ACCOUNT), vote_index, INITIAL_USER_TWO_AADHAAR, Default::default()),
+            Error::<Test>::NoVoteWeight,
+        );
+        assert_eq!(Candidates::<Test>::get(vote_index, INITIAL_USER_TWO_AADHAAR).vote_count, 10);
+	})
+}
+
+#[test]
+fn test_migration_v2_widens_tallies() {
+	new_test_ext().execute_with(|| {
+        let vote_index = 0;
+        StorageVersion::new(1).put::<Ballot>();
+
+        // Write the u64 layout of v1
+        frame_support::storage::unhashed::put_raw(
+            &Candidates::<Test>::hashed_key_for(vote_index, INITIAL_USER_TWO_AADHAAR),
+            &(INITIAL_USER_TWO_AADHAAR, 7u64).encode(),
+        );
+        frame_support::storage::unhashed::put_raw(&NotaTallies::<Test>::hashed_key_for(vote_index), &3u64.encode());
+        frame_support::storage::unhashed::put_raw(
+            &ReferendumTallies::<Test>::hashed_key_for(vote_index),
+            &(1u64, 2u64, 4u64).encode(),
+        );
+        frame_support::storage::unhashed::put_raw(
+            &Results::<Test>::hashed_key_for(vote_index),
+            &(vec![(INITIAL_USER_TWO_AADHAAR, 7u64)], 3u64, vec![INITIAL_USER_TWO_AADHAAR], 10u32, false).encode(),
+        );
+
+        crate::migrations::v2::migrate::<Test>();
+
+        assert_eq!(Ballot::on_chain_storage_version(), 2);
+        let candidate = Candidate { aadhaar_id: INITIAL_USER_TWO_AADHAAR, vote_count: 7 };
+        assert_eq!(Candidates::<Test>::get(vote_index, INITIAL_USER_TWO_AADHAAR), candidate);
+        assert_eq!(NotaTallies::<Test>::get(vote_index), 3);
+        assert_eq!(ReferendumTallies::<Test>::get(vote_index), ReferendumTally { yes: 1, no: 2, abstain: 4 });
+        assert_eq!(Results::<Test>::get(vote_index), Some(ElectionResult {
+            standings: vec![candidate],
+            nota: 3,
+            winners: vec![INITIAL_USER_TWO_AADHAAR],
+            turnout: 10,
+            tied: false,
+            referendum: None,
+        }));
+
+        // Running it again leaves the widened values alone
+        crate::migrations::v2::migrate::<Test>();
+        assert_eq!(NotaTallies::<Test>::get(vote_index), 3);
+	})
+}
+
+#[test]
+fn test_ballot_api_queries() {
+	new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let (election, referendum) = (0, 1);
+
+        assert_ok!(Ballot::start_voting(Origin::signed(INITIAL_USER_ACCOUNT), Default::default()));
+        assert_ok!(Ballot::add_candidates(
+			Origin::signed(INITIAL_USER_ACCOUNT),
+            election,
+            vec![INITIAL_USER_TWO_ACCOUNT, INITIAL_USER_THREE_ACCOUNT].try_into().unwrap(),
+		));
+        assert_ok!(Ballot::start_referendum(
+			Origin::signed(INITIAL_USER_ACCOUNT),
+			Proposal::Text(b"Lower the voting age to 16".to_vec().try_into().unwrap()),
+			Default::default(),
+		));
+        assert_eq!(Ballot::active_sessions(), vec![election, referendum]);
+
+        assert_ok!(Ballot::vote(Origin::signed(INITIAL_USER_ACCOUNT), election, INITIAL_USER_TWO_AADHAAR, Default::default()));
+        assert_ok!(Ballot::vote(Origin::signed(INITIAL_USER_TWO_ACCOUNT), election, INITIAL_USER_TWO_AADHAAR, Default::default()));
+        assert_ok!(Ballot::vote(Origin::signed(INITIAL_USER_THREE_ACCOUNT), election, NOTA, Default::default()));
+        assert_ok!(Ballot::cast_ballot(
+			Origin::signed(INITIAL_USER_ACCOUNT),
+            referendum,
+            BallotChoice::Referendum(ReferendumChoice::No),
+            Default::default(),
+		));
+
+        assert_eq!(Ballot::session_info(election), Some(SessionInfo {
+            vote_index: election,
+            state: VoteState::Voting,
+            ballot_type: BallotType::FirstPastThePost,
+            commissioner: Some(INITIAL_USER_AADHAAR),
+            starts_at: None,
+            ends_at: None,
+            secret: false,
+            majority: None,
+            constituencies: vec![],
+            electoral_roll: None,
+            turnout: 3,
+            parent: None,
+            runoff: None,
+        }));
+        assert_eq!(Ballot::session_info(2), None);
+        let standings = vec![
+            Candidate { aadhaar_id: INITIAL_USER_TWO_AADHAAR, vote_count: 2 },
+            Candidate { aadhaar_id: INITIAL_USER_THREE_AADHAAR, vote_count: 0 },
+        ];
+        assert_eq!(Ballot::candidate_tallies(election), standings);
+        assert_eq!(Ballot::nota_tally(election), 1);
+        assert_eq!(Ballot::referendum_tally(referendum), ReferendumTally { yes: 0, no: 1, abstain: 0 });
+        assert_eq!(Ballot::proposal_info(referendum), Some(ProposalInfo::Text(b"Lower the voting age to 16".to_vec())));
+        assert_eq!(Ballot::proposal_info(election), None);
+
+        assert_ok!(Ballot::stop_voting(Origin::signed(INITIAL_USER_ACCOUNT), election));
+        assert_eq!(Ballot::active_sessions(), vec![referendum]);
+
+        // Archived sessions are answered from their result
+        run_to_block(51);
+        assert_eq!(VotingState::<Test>::get(election), VoteState::Archived);
+        assert_eq!(Candidates::<Test>::iter_prefix(election).count(), 0);
+        assert_eq!(Ballot::candidate_tallies(election), standings);
+        assert_eq!(Ballot::session_info(election).unwrap().state, VoteState::Archived);
+	})
+}
+
+#[test]
+fn test_locks_stay_on_funding_account_after_rotation() {
+	new_test_ext().execute_with(|| {
+        let (weighted, nominating) = (0, 1);
+        let (new_account_id, unfunded_account_id) = (4, 5);
+
+        assert_ok!(Ballot::start_voting(
+			Origin::signed(INITIAL_USER_ACCOUNT),
+			SessionConfig { weight_source: WeightSource::LockedBalance, ..Default::default() },
+		));
+        assert_ok!(Ballot::add_candidates(
+			Origin::signed(INITIAL_USER_ACCOUNT),
+            weighted,
+            vec![INITIAL_USER_TWO_ACCOUNT, INITIAL_USER_THREE_ACCOUNT].try_into().unwrap(),
+		));
+        assert_ok!(Ballot::start_voting(
+			Origin::signed(INITIAL_USER_ACCOUNT),
+			SessionConfig { starts_at: Some(5), ..Default::default() },
+		));
+        assert_ok!(Ballot::lock_vote_weight(Origin::signed(INITIAL_USER_ACCOUNT), weighted, 300));
+        assert_ok!(Ballot::lock_vote_weight(Origin::signed(INITIAL_USER_TWO_ACCOUNT), weighted, 200));
+        assert_ok!(Ballot::nominate(Origin::signed(INITIAL_USER_ACCOUNT), nominating));
+
+        Balances::make_free_balance_be(&new_account_id, 500);
+        assert_ok!(Aadhaar::rotate_account(
+			Origin::signed(INITIAL_USER_ACCOUNT),
+            INITIAL_USER_AADHAAR,
+            new_account_id,
+		));
+        assert_ok!(Aadhaar::rotate_account(
+			Origin::signed(INITIAL_USER_TWO_ACCOUNT),
+            INITIAL_USER_TWO_AADHAAR,
+            unfunded_account_id,
+		));
+
+        // The funds stay locked on the old accounts and the new ones cannot lock again
+        assert_eq!(LockFunders::<Test>::get(INITIAL_USER_AADHAAR, weighted), Some(INITIAL_USER_ACCOUNT));
+        assert_eq!(VoteLocks::<Test>::get(INITIAL_USER_ACCOUNT, weighted), Some(300));
+        assert_eq!(VoteLocks::<Test>::get(new_account_id, weighted), None);
+        assert_eq!(Balances::usable_balance(INITIAL_USER_ACCOUNT), INITIAL_BALANCE - NOMINATION_DEPOSIT - 300);
+        assert_eq!(Balances::usable_balance(INITIAL_USER_TWO_ACCOUNT), INITIAL_BALANCE - 200);
+        assert_eq!(Balances::usable_balance(new_account_id), 500);
+        assert_eq!(Balances::usable_balance(unfunded_account_id), 0);
+        assert_noop!(
+            Ballot::lock_vote_weight(Origin::signed(new_account_id), weighted, 300),
+            Error::<Test>::VoteWeightAlreadyLocked
+        );
+
+        // The votes are still weighted by the balance locked on the old accounts
+        assert_ok!(Ballot::vote(Origin::signed(new_account_id), weighted, INITIAL_USER_TWO_AADHAAR, Default::default()));
+        assert_ok!(Ballot::vote(Origin::signed(unfunded_account_id), weighted, INITIAL_USER_THREE_AADHAAR, Default::default()));
+        assert_eq!(Candidates::<Test>::get(weighted, INITIAL_USER_TWO_AADHAAR).vote_count, 300);
+        assert_eq!(Candidates::<Test>::get(weighted, INITIAL_USER_THREE_AADHAAR).vote_count, 200);
+
+        // The current accounts release the locks held by the old ones
+        assert_ok!(Ballot::stop_voting(Origin::signed(new_account_id), weighted));
+        assert_ok!(Ballot::unlock_vote_weight(Origin::signed(new_account_id), weighted));
+        assert_ok!(Ballot::unlock_vote_weight(Origin::signed(unfunded_account_id), weighted));
+        assert_eq!(LockFunders::<Test>::get(INITIAL_USER_AADHAAR, weighted), None);
+        assert_eq!(Balances::usable_balance(INITIAL_USER_ACCOUNT), INITIAL_BALANCE - NOMINATION_DEPOSIT);
+        assert_eq!(Balances::usable_balance(INITIAL_USER_TWO_ACCOUNT), INITIAL_BALANCE);
+        assert_eq!(Balances::usable_balance(new_account_id), 500);
+        assert_eq!(Balances::usable_balance(unfunded_account_id), 0);
+
+        // The deposit reserved by the old account is returned to the new one
+        assert_ok!(Ballot::withdraw_nomination(Origin::signed(new_account_id), nominating));
+        assert_eq!(Balances::reserved_balance(INITIAL_USER_ACCOUNT), 0);
+        assert_eq!(Balances::free_balance(INITIAL_USER_ACCOUNT), INITIAL_BALANCE - NOMINATION_DEPOSIT);
+        assert_eq!(Balances::free_balance(new_account_id), 500 + NOMINATION_DEPOSIT);
 	})
 }
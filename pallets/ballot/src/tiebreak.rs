@@ -0,0 +1,106 @@
+use super::*;
+use sp_runtime::traits::TrailingZeroInput;
+
+/// Tie-breaking between candidates sharing the lead of a decided session
+///
+/// A lot is drawn from a seed nobody knows when the tie is found. Commission
+/// members commit to secrets until commit_until and reveal them until
+/// draw_at, each revealed secret is folded into the seed. At draw_at the
+/// parent hash of the block is mixed in as well, so the seed is still
+/// unpredictable when no member reveals
+impl<T: Config> Pallet<T> {
+
+	/// Break a tie according to the session's policy, returns false when the session stays tied
+	pub(crate) fn start_tie_break(vote_index: VoteIndex, config: SessionConfig<T>, tied: Vec<AadhaarId>) -> bool {
+		let method = config.tie_break.clone();
+		match method {
+			TieBreak::NoWinner => return false,
+			TieBreak::Repoll => {
+				let repoll_index = Self::start_runoff(vote_index, config, &tied, frame_system::Pallet::<T>::block_number());
+				TieBreakRecords::<T>::insert(vote_index, TieBreakRecord { method, seed: None, winner: None });
+				Self::deposit_event(Event::RepollStarted { vote_index, repoll_index });
+			},
+			TieBreak::Commission | TieBreak::Lot => {
+				let now = frame_system::Pallet::<T>::block_number();
+				let commit_until = now.saturating_add(T::TieBreakDelay::get());
+				let mut draw_at = commit_until.saturating_add(T::TieBreakDelay::get());
+				if method == TieBreak::Lot {
+					// Without room in the schedule the lot is drawn at the next free block
+					while ScheduledDraws::<T>::try_mutate(draw_at, |queue| queue.try_push(vote_index)).is_err() {
+						draw_at = draw_at.saturating_add(One::one());
+					}
+				}
+
+				PendingTieBreaks::<T>::insert(vote_index, PendingTieBreak {
+					method: method.clone(),
+					tied: tied.clone(),
+					commit_until,
+					draw_at,
+					seed: Default::default(),
+				});
+				Self::deposit_event(Event::TieBreakPending { vote_index, method, candidates: tied });
+			},
+		}
+
+		true
+	}
+
+	/// Fold a revealed commission secret into the seed of a pending lot
+	pub(crate) fn do_reveal_tie_seed(account_id: &T::AccountId, vote_index: VoteIndex, secret: Salt) -> DispatchResult {
+		let now = frame_system::Pallet::<T>::block_number();
+		let mut pending = PendingTieBreaks::<T>::get(vote_index).ok_or(Error::<T>::NoTieToBreak)?;
+		ensure!(pending.method == TieBreak::Lot, Error::<T>::TieBreakMethodMismatch);
+		ensure!(now >= pending.commit_until && now < pending.draw_at, Error::<T>::TieSeedRevealNotActive);
+
+		let commitment = TieSeedCommitments::<T>::take(vote_index, account_id).ok_or(Error::<T>::CommitmentNotFound)?;
+		ensure!(T::Hashing::hash_of(&secret) == commitment, Error::<T>::CommitmentMismatch);
+
+		pending.seed = T::Hashing::hash_of(&(pending.seed, secret));
+		PendingTieBreaks::<T>::insert(vote_index, pending);
+
+		Ok(())
+	}
+
+	/// Draw the winner of a pending lot from the revealed secrets and the parent block hash
+	pub(crate) fn draw_lot(vote_index: VoteIndex) {
+		let pending = match PendingTieBreaks::<T>::take(vote_index) {
+			Some(pending) => pending,
+			None => return,
+		};
+		let _ = TieSeedCommitments::<T>::clear_prefix(vote_index, u32::MAX, None);
+
+		let seed = T::Hashing::hash_of(&(pending.seed, frame_system::Pallet::<T>::parent_hash()));
+		let random = u64::decode(&mut TrailingZeroInput::new(seed.as_ref())).unwrap_or_default();
+		let winner = match pending.tied.len() {
+			0 => None,
+			len => pending.tied.get((random % len as u64) as usize).copied(),
+		};
+
+		Self::complete_tie_break(vote_index, pending.method, Some(seed), winner);
+	}
+
+	/// Store how the tie was broken and declare the winner
+	pub(crate) fn complete_tie_break(
+		vote_index: VoteIndex,
+		method: TieBreak,
+		seed: Option<T::Hash>,
+		winner: Option<AadhaarId>,
+	) {
+		TieBreakRecords::<T>::insert(vote_index, TieBreakRecord { method, seed, winner });
+		// The result names the winner of the tie instead of every tied candidate
+		if let Some(winner) = winner {
+			Results::<T>::mutate(vote_index, |result| {
+				if let Some(result) = result {
+					result.winners = vec![winner];
+					result.tied = false;
+				}
+			});
+		}
+		Self::deposit_event(Event::TieBroken { vote_index, winner, seed });
+		Self::deposit_event(Event::ElectionDecided {
+			vote_index,
+			first_round: Self::first_round(vote_index),
+			winner,
+		});
+	}
+}
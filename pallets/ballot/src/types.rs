@@ -1,16 +1,474 @@
 use super::*;
+use frame_support::{CloneNoBound, DefaultNoBound, EqNoBound, PartialEqNoBound, RuntimeDebugNoBound};
 
 pub type VoteIndex = u32;
 
+/// Votes counted for a candidate, wide enough for balance weighted votes
+pub type VoteCount = u128;
+
+/// Random salt mixed into a secret ballot commitment
+pub type Salt = [u8; 32];
+
+/// Built-in None of the Above slot of every session, no aadhaar is issued with this id
+pub const NOTA: AadhaarId = [0u8; 16];
+
+/// Political party in the commission's party registry
+pub type PartyId = u32;
+
+/// Election symbol printed next to a candidate on the ballot
+pub type SymbolId = u32;
+
+/// Balance of the currency nomination deposits are reserved in
+pub type BalanceOf<T> =
+    <<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
+
+/// Imbalance created when a nomination deposit is slashed
+pub type NegativeImbalanceOf<T> =
+    <<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::NegativeImbalance;
+
+/// Sibling hashes proving an aadhaar id is on a session's electoral roll
+pub type RollProof<T> = BoundedVec<<T as frame_system::Config>::Hash, <T as Config>::MaxRollProofDepth>;
+
 #[derive(Encode, Decode, Clone, PartialEq, Eq, Debug, TypeInfo, MaxEncodedLen)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum VoteState {
     Idle,
     Voting,
     Ended,
+    /// Secret ballot session accepting vote commitments
+    Committing,
+    /// Secret ballot session accepting reveals of committed votes
+    Revealing,
+    /// Voting closed and ballots are being counted across blocks
+    Tallying,
+    /// Session was reset and its voter data is being removed across blocks
+    Purging,
+    /// Retention period of the decided session passed, only its result is kept
+    Archived,
+}
+
+/// How voters express their choice on a voting session
+#[derive(Encode, Decode, Clone, PartialEq, Eq, Debug, TypeInfo, MaxEncodedLen)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum BallotType {
+    /// One vote for a single candidate, most votes wins
+    FirstPastThePost,
+    /// Ordered list of candidates counted by instant-runoff elimination
+    RankedChoice,
+    /// One vote for every candidate on the ballot, most votes wins
+    Approval,
+    /// Score between 0 and max_score for candidates on the ballot, highest total wins
+    Score { max_score: u8 },
+    /// Yes, No or Abstain on a proposal instead of a choice between candidates
+    Referendum,
+}
+
+/// Option picked on a referendum
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, Debug, TypeInfo, MaxEncodedLen)]
+pub enum ReferendumChoice {
+    Yes,
+    No,
+    Abstain,
+}
+
+/// Issue put to the voters of a referendum
+#[derive(
+    CloneNoBound, PartialEqNoBound, EqNoBound, RuntimeDebugNoBound,
+    Encode, Decode, TypeInfo, MaxEncodedLen,
+)]
+#[scale_info(skip_type_params(T))]
+#[codec(mel_bound(T: Config))]
+pub enum Proposal<T: Config> {
+    /// Proposal text stored on-chain
+    Text(BoundedVec<u8, T::MaxProposalLength>),
+    /// Hash of a proposal document published off-chain
+    Hash(T::Hash),
+}
+
+/// Votes cast on each option of a referendum
+#[derive(Encode, Decode, Clone, PartialEq, Eq, Debug, TypeInfo, MaxEncodedLen, Default)]
+pub struct ReferendumTally {
+    pub yes: VoteCount,
+    pub no: VoteCount,
+    pub abstain: VoteCount,
+}
+
+/// Decision of a referendum kept in its result
+#[derive(Encode, Decode, Clone, PartialEq, Eq, Debug, TypeInfo, MaxEncodedLen)]
+pub struct ReferendumOutcome {
+    pub approved: bool,
+    pub tally: ReferendumTally,
+}
+
+/// What happens when None of the Above polls more votes than every candidate
+#[derive(Encode, Decode, Clone, PartialEq, Eq, Debug, TypeInfo, MaxEncodedLen)]
+pub enum NotaRule {
+    /// NOTA is only reported, the leading candidate still wins
+    Disregard,
+    /// Session is decided without a winner
+    NoWinner,
+    /// A new session for the seat is scheduled RepollDelay blocks later, without the
+    /// rejected candidates
+    Repoll,
+}
+
+impl Default for NotaRule {
+    fn default() -> Self {
+        NotaRule::Disregard
+    }
+}
+
+/// How a session is decided when candidates share the lead
+#[derive(Encode, Decode, Clone, PartialEq, Eq, Debug, TypeInfo, MaxEncodedLen)]
+pub enum TieBreak {
+    /// Session is decided without a winner
+    NoWinner,
+    /// Commission picks one of the tied candidates with break_tie
+    Commission,
+    /// Lot drawn from commission secrets and a future block hash
+    Lot,
+    /// A new session is started between the tied candidates
+    Repoll,
+}
+
+impl Default for TieBreak {
+    fn default() -> Self {
+        TieBreak::NoWinner
+    }
+}
+
+/// What a vote is weighted by
+#[derive(Encode, Decode, Clone, PartialEq, Eq, Debug, TypeInfo, MaxEncodedLen)]
+pub enum WeightSource {
+    /// One aadhaar, one vote
+    Equal,
+    /// Balance the voter locked for the session with lock_vote_weight
+    LockedBalance,
+    /// Weight table set by the commission before voting opens
+    Commission,
+}
+
+impl Default for WeightSource {
+    fn default() -> Self {
+        WeightSource::Equal
+    }
+}
+
+impl Default for BallotType {
+    fn default() -> Self {
+        BallotType::FirstPastThePost
+    }
+}
+
+/// Ballot cast on sessions which take more than a single candidate
+#[derive(
+    CloneNoBound, PartialEqNoBound, EqNoBound, RuntimeDebugNoBound,
+    Encode, Decode, TypeInfo, MaxEncodedLen,
+)]
+#[scale_info(skip_type_params(T))]
+#[codec(mel_bound(T: Config))]
+pub enum BallotChoice<T: Config> {
+    /// Candidates in order of preference
+    Ranked(BoundedVec<AadhaarId, T::MaxBallotChoices>),
+    /// Candidates the voter approves of, any subset of the session's candidates
+    Approval(BoundedVec<AadhaarId, T::MaxCandidatesPerSession>),
+    /// Score given to each candidate, unlisted candidates score 0
+    Score(BoundedVec<(AadhaarId, u8), T::MaxCandidatesPerSession>),
+    /// Option picked on a referendum
+    Referendum(ReferendumChoice),
 }
 
 #[derive(Encode, Decode, Clone, PartialEq, Eq, Debug, TypeInfo, MaxEncodedLen, Default)]
 pub struct Candidate {
     pub aadhaar_id: AadhaarId,
-    pub vote_count: u16, 
+    pub vote_count: VoteCount,
+}
+
+/// Options chosen by the election commission when starting a voting session
+#[derive(
+    CloneNoBound, PartialEqNoBound, EqNoBound, RuntimeDebugNoBound, DefaultNoBound,
+    Encode, Decode, TypeInfo, MaxEncodedLen,
+)]
+#[scale_info(skip_type_params(T))]
+#[codec(mel_bound(T: Config))]
+pub struct SessionConfig<T: Config> {
+    /// Voters commit `hash(vote_index ++ voter ++ candidate ++ salt)` and reveal it after voting stops
+    pub secret: bool,
+    /// Block at which voting opens, the session opens immediately when not set
+    pub starts_at: Option<T::BlockNumber>,
+    /// Block at which voting closes, the session waits for stop_voting when not set
+    pub ends_at: Option<T::BlockNumber>,
+    /// Block at which the reveal phase of a secret ballot closes, needs ends_at. The
+    /// session waits for stop_voting to end the reveal phase when not set
+    pub reveal_ends_at: Option<T::BlockNumber>,
+    /// Share of the votes cast a candidate needs to exceed to win, every session has a winner
+    /// when not set. On a referendum it is the share of Yes and No votes Yes needs to exceed
+    pub majority: Option<Perbill>,
+    /// Number of leading candidates carried into a runoff when nobody reaches the majority
+    pub runoff_size: u32,
+    /// How voters express their choice
+    pub ballot_type: BallotType,
+    /// Constituencies whose voters make up the electorate, open to every voter when empty
+    pub constituencies: BoundedVec<ConstituencyId, T::MaxSessionConstituencies>,
+    /// Merkle root of the aadhaar ids on the electoral roll, voters attach a membership proof
+    /// to their vote when set
+    pub electoral_roll: Option<T::Hash>,
+    /// What happens when None of the Above wins
+    pub nota_rule: NotaRule,
+    /// How the session is decided when candidates share the lead
+    pub tie_break: TieBreak,
+    /// Voters may replace their vote while voting is open, only the last one is counted
+    pub allow_recast: bool,
+    /// What votes are weighted by on single choice sessions and referendums
+    pub weight_source: WeightSource,
+}
+
+/// Commitments made and revealed on a secret ballot session
+#[derive(Encode, Decode, Clone, PartialEq, Eq, Debug, TypeInfo, MaxEncodedLen, Default)]
+pub struct SecretTally {
+    pub committed: u32,
+    pub revealed: u32,
+}
+
+impl SecretTally {
+    /// Commitments which were never revealed and are therefore not counted
+    pub fn unrevealed(&self) -> u32 {
+        self.committed.saturating_sub(self.revealed)
+    }
+}
+
+/// Progress of the instant-runoff count of a ranked choice session
+#[derive(Encode, Decode, Clone, PartialEq, Eq, Debug, TypeInfo, MaxEncodedLen, Default)]
+pub struct RankedTally {
+    /// Current elimination round, starting at 1
+    pub round: u32,
+    /// Next ballot to count in this round
+    pub cursor: u32,
+    /// Ballots with no remaining candidate in this round
+    pub exhausted: u32,
+}
+
+/// Voter keyed storage of a session, removed one map after the other when it is reset
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, Debug, TypeInfo, MaxEncodedLen)]
+pub enum PurgeStage {
+    Votes,
+    VotedFor,
+    VoteWeights,
+    Commitments,
+    RankedBallots,
+}
+
+impl Default for PurgeStage {
+    fn default() -> Self {
+        PurgeStage::Votes
+    }
+}
+
+impl PurgeStage {
+    /// Map removed after this one, None once every map is gone
+    pub fn next(self) -> Option<Self> {
+        match self {
+            PurgeStage::Votes => Some(PurgeStage::VotedFor),
+            PurgeStage::VotedFor => Some(PurgeStage::VoteWeights),
+            PurgeStage::VoteWeights => Some(PurgeStage::Commitments),
+            PurgeStage::Commitments => Some(PurgeStage::RankedBallots),
+            PurgeStage::RankedBallots => None,
+        }
+    }
+}
+
+/// Progress of the removal of a reset session
+#[derive(Encode, Decode, Clone, PartialEq, Eq, Debug, TypeInfo, Default)]
+pub struct PurgeProgress {
+    /// Map being removed
+    pub stage: PurgeStage,
+    /// Cursor returned by the last clear_prefix on the map
+    pub cursor: Option<Vec<u8>>,
+}
+
+/// Append-only Merkle tree of the voters of a session, leaves in the order voters cast
+/// their first ballot. Only the last left node of every level is kept
+#[derive(CloneNoBound, PartialEqNoBound, EqNoBound, RuntimeDebugNoBound, DefaultNoBound, Encode, Decode, TypeInfo, MaxEncodedLen)]
+#[scale_info(skip_type_params(T))]
+#[codec(mel_bound(T: Config))]
+pub struct VoterTree<T: Config> {
+    /// Left node waiting for its right sibling, by height
+    pub branch: BoundedVec<T::Hash, T::MaxRollProofDepth>,
+    /// Number of leaves appended
+    pub count: u32,
+}
+
+/// Outcome of one instant-runoff round
+#[derive(Encode, Decode, Clone, PartialEq, Eq, Debug, TypeInfo, MaxEncodedLen)]
+pub struct EliminationRound {
+    /// Candidate with the most votes in the round
+    pub leader: AadhaarId,
+    pub leader_votes: u32,
+    /// Candidate with the fewest votes, none in the final round
+    pub eliminated: Option<AadhaarId>,
+    pub eliminated_votes: u32,
+    /// Ballots with no remaining candidate
+    pub exhausted: u32,
+}
+
+/// Self-nomination of a registered aadhaar holder for a voting session
+#[derive(
+    CloneNoBound, PartialEqNoBound, EqNoBound, RuntimeDebugNoBound,
+    Encode, Decode, TypeInfo, MaxEncodedLen,
+)]
+#[scale_info(skip_type_params(T))]
+#[codec(mel_bound(T: Config))]
+pub struct Nomination<T: Config> {
+    /// Account the deposit is reserved from
+    pub account_id: T::AccountId,
+    /// Deposit returned or slashed once the session is decided
+    pub deposit: BalanceOf<T>,
+    /// Accepted by the commission and standing as a candidate
+    pub accepted: bool,
+}
+
+/// Political party registered by the election commission
+#[derive(
+    CloneNoBound, PartialEqNoBound, EqNoBound, RuntimeDebugNoBound,
+    Encode, Decode, TypeInfo, MaxEncodedLen,
+)]
+#[scale_info(skip_type_params(T))]
+#[codec(mel_bound(T: Config))]
+pub struct Party<T: Config> {
+    pub name: BoundedVec<u8, T::MaxNameLength>,
+    /// Symbol reserved for the party's candidates
+    pub symbol: SymbolId,
+}
+
+/// Details shown for a candidate on the ballot
+#[derive(
+    CloneNoBound, PartialEqNoBound, EqNoBound, RuntimeDebugNoBound,
+    Encode, Decode, TypeInfo, MaxEncodedLen,
+)]
+#[scale_info(skip_type_params(T))]
+#[codec(mel_bound(T: Config))]
+pub struct CandidateProfile<T: Config> {
+    /// Display name of the candidate
+    pub name: BoundedVec<u8, T::MaxNameLength>,
+    /// Party the candidate stands for, independent when not set
+    pub party: Option<PartyId>,
+    /// Election symbol of the candidate
+    pub symbol: SymbolId,
+    /// Content identifier of the manifesto, such as an IPFS CID
+    pub manifesto: Option<BoundedVec<u8, T::MaxManifestoLength>>,
+}
+
+/// Outcome of a decided voting session, written once when the session ends
+/// and narrowed to a single winner when its tie is broken
+#[derive(Encode, Decode, Clone, PartialEq, Eq, Debug, TypeInfo, Default)]
+pub struct ElectionResult {
+    /// Candidates ordered by votes, ties ordered by aadhaar id
+    pub standings: Vec<Candidate>,
+    /// Votes for None of the Above
+    pub nota: VoteCount,
+    /// Leading candidates declared elected, empty when the seat went to a runoff or repoll
+    pub winners: Vec<AadhaarId>,
+    /// Ballots cast on the session
+    pub turnout: u32,
+    /// More than one candidate shares the lead and no tie-break picked a winner
+    pub tied: bool,
+    /// Outcome of a referendum, None for candidate elections
+    pub referendum: Option<ReferendumOutcome>,
+}
+
+/// Proposal of a referendum served by the ballot runtime API
+#[derive(Encode, Decode, Clone, PartialEq, Eq, Debug, TypeInfo)]
+pub enum ProposalInfo<Hash> {
+    /// Proposal text stored on-chain
+    Text(Vec<u8>),
+    /// Hash of a proposal document published off-chain
+    Hash(Hash),
+}
+
+/// Overview of a voting session served by the ballot runtime API
+#[derive(Encode, Decode, Clone, PartialEq, Eq, Debug, TypeInfo)]
+pub struct SessionInfo<BlockNumber, Hash> {
+    pub vote_index: VoteIndex,
+    pub state: VoteState,
+    pub ballot_type: BallotType,
+    /// Aadhaar id of the commissioner who started the session
+    pub commissioner: Option<AadhaarId>,
+    pub starts_at: Option<BlockNumber>,
+    pub ends_at: Option<BlockNumber>,
+    pub secret: bool,
+    pub majority: Option<Perbill>,
+    pub constituencies: Vec<ConstituencyId>,
+    pub electoral_roll: Option<Hash>,
+    /// Ballots cast so far
+    pub turnout: u32,
+    /// Session this session is the runoff of
+    pub parent: Option<VoteIndex>,
+    /// Runoff started from this session
+    pub runoff: Option<VoteIndex>,
+}
+
+/// Tie waiting for the commission or a lot to pick the winner
+#[derive(
+    CloneNoBound, PartialEqNoBound, EqNoBound, RuntimeDebugNoBound,
+    Encode, Decode, TypeInfo,
+)]
+#[scale_info(skip_type_params(T))]
+pub struct PendingTieBreak<T: Config> {
+    pub method: TieBreak,
+    /// Candidates sharing the lead
+    pub tied: Vec<AadhaarId>,
+    /// Commission members commit to lot secrets before this block
+    pub commit_until: T::BlockNumber,
+    /// Lot is drawn at this block, secrets are revealed before it
+    pub draw_at: T::BlockNumber,
+    /// Revealed secrets folded into a single hash
+    pub seed: T::Hash,
+}
+
+/// How a tie was broken, kept for audit
+#[derive(
+    CloneNoBound, PartialEqNoBound, EqNoBound, RuntimeDebugNoBound,
+    Encode, Decode, TypeInfo, MaxEncodedLen,
+)]
+#[scale_info(skip_type_params(T))]
+#[codec(mel_bound(T: Config))]
+pub struct TieBreakRecord<T: Config> {
+    pub method: TieBreak,
+    /// Seed the lot was drawn from
+    pub seed: Option<T::Hash>,
+    /// Candidate picked, none when the tie went to a repoll
+    pub winner: Option<AadhaarId>,
+}
+
+/// Sessions a voting delegate may vote on
+#[derive(Encode, Decode, Clone, PartialEq, Eq, Debug, TypeInfo, MaxEncodedLen)]
+pub enum DelegationScope {
+    /// Only the given voting session
+    Session(VoteIndex),
+    /// Every voting session
+    AllSessions,
+}
+
+impl DelegationScope {
+    /// Check the scope covers the given voting session
+    pub fn covers(&self, vote_index: VoteIndex) -> bool {
+        match self {
+            DelegationScope::Session(index) => *index == vote_index,
+            DelegationScope::AllSessions => true,
+        }
+    }
+}
+
+/// Assistant allowed to submit votes on behalf of an aadhaar holder
+#[derive(
+    CloneNoBound, PartialEqNoBound, EqNoBound, RuntimeDebugNoBound,
+    Encode, Decode, TypeInfo, MaxEncodedLen,
+)]
+#[scale_info(skip_type_params(T))]
+#[codec(mel_bound(T: Config))]
+pub struct Delegation<T: Config> {
+    pub delegate: T::AccountId,
+    pub scope: DelegationScope,
+    /// Delegation can no longer be used from this block
+    pub expires_at: T::BlockNumber,
 }
@@ -0,0 +1,196 @@
+//! Weights for pallet_ballot
+//!
+//! These are hand estimates, not benchmark results. Regenerate this file from
+//! the benchmarks in benchmarking.rs on reference hardware with
+//! `benchmark pallet --pallet pallet_ballot --extrinsic '*'` before relying on them
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{traits::Get, weights::{Weight, constants::RocksDbWeight}};
+use sp_std::marker::PhantomData;
+
+/// Weight functions needed for pallet_ballot.
+pub trait WeightInfo {
+	fn start_voting(k: u32, ) -> Weight;
+	fn add_candidates(c: u32, ) -> Weight;
+	fn stop_voting(c: u32, ) -> Weight;
+	fn reset_voting(c: u32, ) -> Weight;
+	fn vote(d: u32, ) -> Weight;
+	fn cast_ballot(c: u32, d: u32, ) -> Weight;
+	fn commit_vote(d: u32, ) -> Weight;
+}
+
+/// Weights for pallet_ballot using the Substrate node and recommended hardware.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+	// Storage: Ballot CurrentVoteIndex (r:1 w:1)
+	// Storage: Aadhaar RLookup (r:1 w:0)
+	// Storage: Aadhaar Constituencies (r:1 w:0)
+	// Storage: Ballot ScheduledStarts (r:1 w:1)
+	// Storage: Ballot ScheduledEnds (r:2 w:2)
+	// Storage: Ballot VotingState (r:0 w:1)
+	// Storage: Ballot Sessions (r:0 w:1)
+	// Storage: Ballot ChiefCommissioner (r:0 w:1)
+	fn start_voting(k: u32, ) -> Weight {
+		(21_470_000 as Weight)
+			.saturating_add((1_850_000 as Weight).saturating_mul(k as Weight))
+			.saturating_add(T::DbWeight::get().reads(5 as Weight))
+			.saturating_add(T::DbWeight::get().reads((1 as Weight).saturating_mul(k as Weight)))
+			.saturating_add(T::DbWeight::get().writes(7 as Weight))
+	}
+	// Storage: Ballot Sessions (r:1 w:0)
+	// Storage: Ballot VotingState (r:1 w:0)
+	// Storage: Ballot Turnout (r:1 w:0)
+	// Storage: Aadhaar RLookup (r:1 w:0)
+	// Storage: Ballot Candidates (r:1 w:1)
+	// Storage: Ballot Nominations (r:1 w:0)
+	// Storage: Ballot CandidateCount (r:1 w:1)
+	fn add_candidates(c: u32, ) -> Weight {
+		(14_218_000 as Weight)
+			.saturating_add((9_874_000 as Weight).saturating_mul(c as Weight))
+			.saturating_add(T::DbWeight::get().reads(4 as Weight))
+			.saturating_add(T::DbWeight::get().reads((3 as Weight).saturating_mul(c as Weight)))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes((1 as Weight).saturating_mul(c as Weight)))
+	}
+	// Storage: Ballot ChiefCommissioner (r:1 w:0)
+	// Storage: Ballot VotingState (r:1 w:1)
+	// Storage: Ballot Sessions (r:1 w:0)
+	// Storage: Ballot Candidates (r:1 w:0)
+	// Storage: Ballot Nominations (r:1 w:1)
+	// Storage: Ballot NotaTallies (r:1 w:0)
+	// Storage: Ballot Turnout (r:1 w:0)
+	// Storage: Ballot Results (r:0 w:1)
+	fn stop_voting(c: u32, ) -> Weight {
+		(29_503_000 as Weight)
+			.saturating_add((7_162_000 as Weight).saturating_mul(c as Weight))
+			.saturating_add(T::DbWeight::get().reads(5 as Weight))
+			.saturating_add(T::DbWeight::get().reads((2 as Weight).saturating_mul(c as Weight)))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes((1 as Weight).saturating_mul(c as Weight)))
+	}
+	// Storage: Ballot Results (r:1 w:0)
+	// Storage: Aadhaar RLookup (r:1 w:0)
+	// Storage: Ballot Nominations (r:1 w:1)
+	// Storage: Ballot Candidates (r:0 w:1)
+	// Storage: Ballot CandidateProfiles (r:0 w:1)
+	// Storage: Ballot CandidateCount (r:0 w:1)
+	fn reset_voting(c: u32, ) -> Weight {
+		(35_806_000 as Weight)
+			.saturating_add((4_391_000 as Weight).saturating_mul(c as Weight))
+			.saturating_add(T::DbWeight::get().reads(3 as Weight))
+			.saturating_add(T::DbWeight::get().writes(16 as Weight))
+			.saturating_add(T::DbWeight::get().writes((3 as Weight).saturating_mul(c as Weight)))
+	}
+	// Storage: Aadhaar RLookup (r:1 w:0)
+	// Storage: Ballot Candidates (r:1 w:1)
+	// Storage: Ballot VotingState (r:1 w:0)
+	// Storage: Ballot Sessions (r:1 w:0)
+	// Storage: Aadhaar ConstituencyOf (r:1 w:0)
+	// Storage: Ballot LockFunders (r:1 w:0)
+	// Storage: Ballot VoteLocks (r:1 w:0)
+	// Storage: Ballot VotedFor (r:1 w:1)
+	// Storage: Ballot NotaTallies (r:1 w:1)
+	// Storage: Ballot Votes (r:1 w:1)
+	// Storage: Ballot Turnout (r:1 w:1)
+	// Storage: Ballot VoterTrees (r:1 w:1)
+	fn vote(d: u32, ) -> Weight {
+		(38_920_000 as Weight)
+			.saturating_add((2_410_000 as Weight).saturating_mul(d as Weight))
+			.saturating_add(T::DbWeight::get().reads(12 as Weight))
+			.saturating_add(T::DbWeight::get().writes(6 as Weight))
+	}
+	// Storage: Aadhaar RLookup (r:1 w:0)
+	// Storage: Ballot VotingState (r:1 w:0)
+	// Storage: Ballot Sessions (r:1 w:0)
+	// Storage: Aadhaar ConstituencyOf (r:1 w:0)
+	// Storage: Ballot Votes (r:1 w:1)
+	// Storage: Ballot Candidates (r:1 w:1)
+	// Storage: Ballot RankedBallotCount (r:1 w:1)
+	// Storage: Ballot LockFunders (r:1 w:0)
+	// Storage: Ballot VoteLocks (r:1 w:0)
+	// Storage: Ballot Turnout (r:1 w:1)
+	// Storage: Ballot VoterTrees (r:1 w:1)
+	// Storage: Ballot RankedBallots (r:0 w:1)
+	fn cast_ballot(c: u32, d: u32, ) -> Weight {
+		(36_240_000 as Weight)
+			.saturating_add((6_310_000 as Weight).saturating_mul(c as Weight))
+			.saturating_add((2_410_000 as Weight).saturating_mul(d as Weight))
+			.saturating_add(T::DbWeight::get().reads(10 as Weight))
+			.saturating_add(T::DbWeight::get().reads((1 as Weight).saturating_mul(c as Weight)))
+			.saturating_add(T::DbWeight::get().writes(5 as Weight))
+			.saturating_add(T::DbWeight::get().writes((1 as Weight).saturating_mul(c as Weight)))
+	}
+	// Storage: Aadhaar RLookup (r:1 w:0)
+	// Storage: Ballot VotingState (r:1 w:0)
+	// Storage: Ballot Sessions (r:1 w:0)
+	// Storage: Aadhaar ConstituencyOf (r:1 w:0)
+	// Storage: Ballot Votes (r:1 w:1)
+	// Storage: Ballot Turnout (r:1 w:1)
+	// Storage: Ballot VoterTrees (r:1 w:1)
+	// Storage: Ballot SecretTallies (r:1 w:1)
+	// Storage: Ballot Commitments (r:0 w:1)
+	fn commit_vote(d: u32, ) -> Weight {
+		(33_580_000 as Weight)
+			.saturating_add((2_410_000 as Weight).saturating_mul(d as Weight))
+			.saturating_add(T::DbWeight::get().reads(8 as Weight))
+			.saturating_add(T::DbWeight::get().writes(5 as Weight))
+	}
+}
+
+// For backwards compatibility and tests
+impl WeightInfo for () {
+	fn start_voting(k: u32, ) -> Weight {
+		(21_470_000 as Weight)
+			.saturating_add((1_850_000 as Weight).saturating_mul(k as Weight))
+			.saturating_add(RocksDbWeight::get().reads(5 as Weight))
+			.saturating_add(RocksDbWeight::get().reads((1 as Weight).saturating_mul(k as Weight)))
+			.saturating_add(RocksDbWeight::get().writes(7 as Weight))
+	}
+	fn add_candidates(c: u32, ) -> Weight {
+		(14_218_000 as Weight)
+			.saturating_add((9_874_000 as Weight).saturating_mul(c as Weight))
+			.saturating_add(RocksDbWeight::get().reads(4 as Weight))
+			.saturating_add(RocksDbWeight::get().reads((3 as Weight).saturating_mul(c as Weight)))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes((1 as Weight).saturating_mul(c as Weight)))
+	}
+	fn stop_voting(c: u32, ) -> Weight {
+		(29_503_000 as Weight)
+			.saturating_add((7_162_000 as Weight).saturating_mul(c as Weight))
+			.saturating_add(RocksDbWeight::get().reads(5 as Weight))
+			.saturating_add(RocksDbWeight::get().reads((2 as Weight).saturating_mul(c as Weight)))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes((1 as Weight).saturating_mul(c as Weight)))
+	}
+	fn reset_voting(c: u32, ) -> Weight {
+		(35_806_000 as Weight)
+			.saturating_add((4_391_000 as Weight).saturating_mul(c as Weight))
+			.saturating_add(RocksDbWeight::get().reads(3 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(16 as Weight))
+			.saturating_add(RocksDbWeight::get().writes((3 as Weight).saturating_mul(c as Weight)))
+	}
+	fn vote(d: u32, ) -> Weight {
+		(38_920_000 as Weight)
+			.saturating_add((2_410_000 as Weight).saturating_mul(d as Weight))
+			.saturating_add(RocksDbWeight::get().reads(12 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(6 as Weight))
+	}
+	fn cast_ballot(c: u32, d: u32, ) -> Weight {
+		(36_240_000 as Weight)
+			.saturating_add((6_310_000 as Weight).saturating_mul(c as Weight))
+			.saturating_add((2_410_000 as Weight).saturating_mul(d as Weight))
+			.saturating_add(RocksDbWeight::get().reads(10 as Weight))
+			.saturating_add(RocksDbWeight::get().reads((1 as Weight).saturating_mul(c as Weight)))
+			.saturating_add(RocksDbWeight::get().writes(5 as Weight))
+			.saturating_add(RocksDbWeight::get().writes((1 as Weight).saturating_mul(c as Weight)))
+	}
+	fn commit_vote(d: u32, ) -> Weight {
+		(33_580_000 as Weight)
+			.saturating_add((2_410_000 as Weight).saturating_mul(d as Weight))
+			.saturating_add(RocksDbWeight::get().reads(8 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(5 as Weight))
+	}
+}
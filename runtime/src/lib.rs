@@ -46,6 +46,10 @@ use pallet_collective::EnsureMember;
 
 /// Import the template pallet.
 pub use pallet_aadhaar;
+use pallet_aadhaar::types::{AadhaarId, Registration};
+use pallet_ballot::types::{
+	Candidate, ElectionResult, ProposalInfo, ReferendumTally, SessionInfo, VoteCount, VoteIndex,
+};
 
 /// An index to a block.
 pub type BlockNumber = u32;
@@ -102,10 +106,10 @@ pub const VERSION: RuntimeVersion = RuntimeVersion {
 	//   `spec_version`, and `authoring_version` are the same between Wasm and native.
 	// This value is set to 100 to notify Polkadot-JS App (https://polkadot.js.org/apps) to use
 	//   the compatible custom types.
-	spec_version: 100,
+	spec_version: 101,
 	impl_version: 1,
 	apis: RUNTIME_API_VERSIONS,
-	transaction_version: 1,
+	transaction_version: 2,
 	state_version: 1,
 };
 
@@ -315,12 +319,39 @@ impl pallet_collective::Config<ElectionCommissionCollective> for Runtime {
 impl pallet_aadhaar::Config for Runtime {
 	type Event = Event;
 	type RegisterOrigin = EnsureMember<AccountId, AkshayaCouncilCollective>;
+	type MaxConstituencyNameLength = ConstU32<64>;
+	type MaxGuardians = ConstU32<8>;
+	type RecoveryDelay = ConstU32<{ 7 * DAYS }>;
+}
+
+parameter_types! {
+	pub const NominationDeposit: Balance = 1_000 * EXISTENTIAL_DEPOSIT;
+	/// Candidates polling less than a sixth of the votes forfeit their deposit
+	pub const DepositReturnThreshold: Perbill = Perbill::from_parts(166_666_667);
 }
 
 /// Configure the pallet-template in pallets/template.
 impl pallet_ballot::Config for Runtime {
 	type Event = Event;
 	type ElectionCommissionOrigin = EnsureMember<AccountId, ElectionCommissionCollective>;
+	type MaxScheduledPerBlock = ConstU32<20>;
+	type MaxBallotChoices = ConstU32<16>;
+	type MaxTallyStepsPerBlock = ConstU32<500>;
+	type MaxProposalLength = ConstU32<1024>;
+	type MaxSessionConstituencies = ConstU32<64>;
+	type MaxRollProofDepth = ConstU32<32>;
+	type Currency = Balances;
+	type NominationDeposit = NominationDeposit;
+	type DepositReturnThreshold = DepositReturnThreshold;
+	type DepositSlash = ();
+	type MaxNameLength = ConstU32<128>;
+	type MaxManifestoLength = ConstU32<64>;
+	type TieBreakDelay = ConstU32<{ 10 * MINUTES }>;
+	type MaxDelegatorsPerDelegate = ConstU32<10>;
+	type RetentionPeriod = ConstU32<{ 90 * DAYS }>;
+	type MaxCandidatesPerSession = ConstU32<64>;
+	type RepollDelay = ConstU32<{ 2 * DAYS }>;
+	type WeightInfo = pallet_ballot::weights::SubstrateWeight<Runtime>;
 }
 
 
@@ -396,6 +427,7 @@ mod benches {
 		[pallet_balances, Balances]
 		[pallet_timestamp, Timestamp]
 		[pallet_aadhaar, Aadhaar]
+		[pallet_ballot, Ballot]
 	);
 }
 
@@ -547,6 +579,54 @@ impl_runtime_apis! {
 		}
 	}
 
+	impl pallet_aadhaar_runtime_api::AadhaarApi<Block, AccountId, BlockNumber> for Runtime {
+		fn account_of(aadhaar_id: AadhaarId) -> Option<AccountId> {
+			Aadhaar::get_account_id(&aadhaar_id)
+		}
+
+		fn id_of(account_id: AccountId) -> Option<AadhaarId> {
+			Aadhaar::get_aadhaar_id(&account_id)
+		}
+
+		fn registration(aadhaar_id: AadhaarId) -> Option<Registration<AccountId, BlockNumber>> {
+			Aadhaar::registration(&aadhaar_id)
+		}
+	}
+
+	impl pallet_ballot_runtime_api::BallotApi<Block, BlockNumber, Hash> for Runtime {
+		fn session(vote_index: VoteIndex) -> Option<SessionInfo<BlockNumber, Hash>> {
+			Ballot::session_info(vote_index)
+		}
+
+		fn candidates(vote_index: VoteIndex) -> Vec<Candidate> {
+			Ballot::candidate_tallies(vote_index)
+		}
+
+		fn has_voted(vote_index: VoteIndex, aadhaar_id: AadhaarId) -> bool {
+			Ballot::has_voted(vote_index, aadhaar_id)
+		}
+
+		fn active_sessions() -> Vec<VoteIndex> {
+			Ballot::active_sessions()
+		}
+
+		fn results(vote_index: VoteIndex) -> Option<ElectionResult> {
+			Ballot::results(vote_index)
+		}
+
+		fn nota_tally(vote_index: VoteIndex) -> VoteCount {
+			Ballot::nota_tally(vote_index)
+		}
+
+		fn referendum_tally(vote_index: VoteIndex) -> ReferendumTally {
+			Ballot::referendum_tally(vote_index)
+		}
+
+		fn proposal(vote_index: VoteIndex) -> Option<ProposalInfo<Hash>> {
+			Ballot::proposal_info(vote_index)
+		}
+	}
+
 	#[cfg(feature = "runtime-benchmarks")]
 	impl frame_benchmarking::Benchmark<Block> for Runtime {
 		fn benchmark_metadata(extra: bool) -> (